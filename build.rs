@@ -0,0 +1,100 @@
+// Finds every `src/yYYYY/dayN.rs` and writes out their modules, plus a
+// calendar for each year, for lib.rs to include. Adding a day is then just a
+// matter of adding its file.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+struct Day {
+    day: u8,
+    path: PathBuf,
+    // whether the module has a `pub fn generate` for the `generate` command
+    generator: bool,
+}
+
+// `prefix` followed by a number, then `suffix`
+fn number<T: std::str::FromStr>(name: &str, prefix: &str, suffix: &str) -> Option<T> {
+    let n = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    if n.is_empty() || !n.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    n.parse().ok()
+}
+
+fn entries(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("couldn't read {}: {e}", dir.display()))
+        .map(|entry| {
+            let entry = entry.unwrap();
+            (
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.path(),
+            )
+        })
+        .collect();
+    entries.sort_unstable();
+    entries
+}
+
+fn main() {
+    let src = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    // a directory counts as changed when anything inside it does
+    println!("cargo:rerun-if-changed=src");
+
+    let mut years: Vec<(u16, Vec<Day>)> = Vec::new();
+    for (name, path) in entries(&src) {
+        let Some(year) = number(&name, "y", "") else {
+            continue;
+        };
+        let mut days: Vec<Day> = entries(&path)
+            .into_iter()
+            .filter_map(|(name, path)| {
+                let day = number(&name, "day", ".rs").filter(|day| (1..=25).contains(day))?;
+                let source = std::fs::read_to_string(&path).unwrap();
+                let generator = source.contains("\npub fn generate(");
+                Some(Day {
+                    day,
+                    path,
+                    generator,
+                })
+            })
+            .collect();
+        days.sort_unstable_by_key(|d| d.day);
+        if !days.is_empty() {
+            years.push((year, days));
+        }
+    }
+    years.sort_unstable_by_key(|&(year, _)| year);
+
+    let mut out = String::new();
+    for (year, days) in &years {
+        writeln!(out, "pub mod y{year} {{").unwrap();
+        for Day { day, path, .. } in days {
+            let path = path.to_str().expect("source paths need to be UTF-8");
+            writeln!(out, "    #[allow(unused)]\n    #[path = {path:?}]").unwrap();
+            writeln!(out, "    pub mod day{day};").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    writeln!(out, "/// Every year with solutions, oldest first").unwrap();
+    writeln!(out, "pub static CALENDARS: &[Calendar] = &[").unwrap();
+    for (year, days) in &years {
+        writeln!(
+            out,
+            "    Calendar {{\n        year: {year},\n        days: &["
+        )
+        .unwrap();
+        for Day { day, .. } in days {
+            writeln!(out, "            &y{year}::day{day}::Day{day},").unwrap();
+        }
+        writeln!(out, "        ],\n        generators: &[").unwrap();
+        for Day { day, .. } in days.iter().filter(|d| d.generator) {
+            writeln!(out, "            ({day}, y{year}::day{day}::generate),").unwrap();
+        }
+        writeln!(out, "        ],\n    }},").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let dest = Path::new(&std::env::var("OUT_DIR").unwrap()).join("calendars.rs");
+    std::fs::write(dest, out).unwrap();
+}
@@ -0,0 +1,121 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation it makes
+///
+/// Only installed with the `count-alloc` feature, as the bookkeeping slows
+/// down allocation-heavy days.
+pub struct CountingAlloc;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+impl CountingAlloc {
+    fn grow(size: usize) {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    // counted as freeing the old block and allocating the new one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new
+    }
+}
+
+/// Whether allocations are actually being counted
+pub const fn enabled() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+/// Allocations made during a call to `measure`
+#[derive(Clone, Copy, Default)]
+pub struct AllocStats {
+    pub allocs: usize,
+    pub bytes: usize,
+    // most memory held at once, on top of what was live before the call
+    pub peak: usize,
+}
+
+/// Format a number of bytes with a sensible unit
+pub fn fmt_bytes(bytes: usize) -> String {
+    if bytes < 1 << 10 {
+        format!("{bytes}B")
+    } else if bytes < 1 << 20 {
+        format!("{:.2}KiB", bytes as f64 / (1 << 10) as f64)
+    } else if bytes < 1 << 30 {
+        format!("{:.2}MiB", bytes as f64 / (1 << 20) as f64)
+    } else {
+        format!("{:.2}GiB", bytes as f64 / (1 << 30) as f64)
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocs,
+            fmt_bytes(self.bytes),
+            fmt_bytes(self.peak)
+        )
+    }
+}
+
+/// Count the allocations made while running `f`
+///
+/// The counters are global, so anything allocating on another thread at the
+/// same time gets counted too.
+pub fn measure<U>(f: impl FnOnce() -> U) -> (AllocStats, U) {
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let ret = f();
+    let stats = AllocStats {
+        allocs: ALLOCS.load(Ordering::Relaxed) - allocs,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (stats, ret)
+}
@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter};
+
+/// The result of solving one part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Wide(u128),
+    Text(String),
+    // The puzzle has no answer for this part, like day 25 part 2
+    Nothing,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Wide(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+            Answer::Nothing => f.pad("-"),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($inner:ty): $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $inner)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Unsigned(u64): u8, u16, u32, u64, usize);
+impl_from!(Signed(i64): i8, i16, i32, i64, isize);
+impl_from!(Wide(u128): u128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
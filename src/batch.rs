@@ -0,0 +1,111 @@
+use crate::check::{self, Tally};
+use crate::solution::{self, Runner};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// `21.txt` and `21-alice.txt` are inputs for day 21, but `2.txt` isn't
+fn is_input_for(name: &str, day: usize) -> bool {
+    name.strip_suffix(".txt")
+        .and_then(|stem| stem.strip_prefix(&day.to_string()))
+        .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Every input for `day` in `dir`, sorted by name
+pub fn input_files(dir: &Path, day: usize) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name.to_str().is_some_and(|name| is_input_for(name, day)) && entry.path().is_file() {
+            files.push(entry.path());
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+/// Where the answers for an input live, e.g. `21-alice.answers` next to
+/// `21-alice.txt`, in the same format as `src/answers/YEAR.txt`
+pub fn answers_path(input: &Path) -> PathBuf {
+    input.with_extension("answers")
+}
+
+/// Run every day in `days` against each of its inputs in `dir`, printing a
+/// row per file and part and giving up on any taking longer than `timeout`
+///
+/// Files without an answers file are only solved. Returns `true` if nothing
+/// failed.
+pub fn check_dir(days: &[&'static dyn Runner], dir: &Path, timeout: Option<Duration>) -> bool {
+    let mut tally = Tally::default();
+    let mut files = 0;
+    println!(
+        "{:>3}  {:<20}  {:>4}  {:<20}  {:<20}  result",
+        "day", "file", "part", "expected", "actual"
+    );
+    for &runner in days {
+        let day = runner.day() as usize;
+        let inputs = match input_files(dir, day) {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                println!("{day:>3}  couldn't read {}: {e}", dir.display());
+                return false;
+            }
+        };
+        for path in inputs {
+            files += 1;
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let prefix = format!("{day:>3}  {name:<20}");
+            let input = match std::fs::read_to_string(&path) {
+                Ok(input) => solution::normalize_input(input),
+                Err(e) => {
+                    println!("{prefix}  {:>4}  {e}", "-");
+                    tally.failed += 1;
+                    continue;
+                }
+            };
+            let sidecar = answers_path(&path);
+            let answers = match std::fs::read_to_string(&sidecar) {
+                Ok(src) => match check::parse_answers(&src) {
+                    Ok(answers) => answers,
+                    Err(e) => {
+                        println!("{prefix}  {:>4}  {}: {e}", "-", sidecar.display());
+                        tally.failed += 1;
+                        continue;
+                    }
+                },
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Default::default(),
+                Err(e) => {
+                    println!("{prefix}  {:>4}  {}: {e}", "-", sidecar.display());
+                    tally.failed += 1;
+                    continue;
+                }
+            };
+            let expected = |part| answers.get(&(day as u8, part)).map(String::as_str);
+            check::check_input(&prefix, runner, &input, expected, timeout, &mut tally);
+        }
+    }
+    println!(
+        "\n{files} inputs, {} passed, {} failed",
+        tally.passed, tally.failed
+    );
+    tally.failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_names() {
+        assert!(is_input_for("21.txt", 21));
+        assert!(is_input_for("21-alice.txt", 21));
+        assert!(!is_input_for("2.txt", 21));
+        assert!(!is_input_for("21.txt", 2));
+        assert!(!is_input_for("21.answers", 21));
+        assert_eq!(
+            answers_path(Path::new("inputs/21-alice.txt")),
+            Path::new("inputs/21-alice.answers")
+        );
+    }
+}
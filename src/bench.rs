@@ -0,0 +1,181 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+// each sample runs the solver enough times to take at least this long, so
+// that very fast solvers aren't just measuring timer resolution
+const MIN_SAMPLE_TIME: Duration = Duration::from_micros(50);
+const WARMUP_ITERATIONS: u32 = 32;
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 100_000;
+
+pub struct BenchConfig {
+    // total time to spend measuring each function
+    pub budget: Duration,
+    // fixed number of samples to take, ignoring `budget`
+    pub iterations: Option<usize>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(5),
+            iterations: None,
+        }
+    }
+}
+
+/// Summary of the per-call times, in nanoseconds
+pub struct Stats {
+    pub samples: usize,
+    // calls made per sample
+    pub batch: u32,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+    pub p95: f64,
+    pub p99: f64,
+    // samples outside the inner (1.5 * IQR) and outer (3 * IQR) fences
+    pub mild_outliers: usize,
+    pub severe_outliers: usize,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<f64>, batch: u32) -> Self {
+        samples.sort_unstable_by(f64::total_cmp);
+        let n = samples.len();
+        // nearest-rank percentile
+        let percentile = |p: f64| samples[((p * n as f64).ceil() as usize).clamp(1, n) - 1];
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        let (q1, q3) = (percentile(0.25), percentile(0.75));
+        let iqr = q3 - q1;
+        let outside = |k: f64| {
+            samples
+                .iter()
+                .filter(|&&s| s < q1 - k * iqr || s > q3 + k * iqr)
+                .count()
+        };
+        let severe_outliers = outside(3.0);
+        Self {
+            samples: n,
+            batch,
+            min: samples[0],
+            median: percentile(0.5),
+            mean,
+            stddev: variance.sqrt(),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            mild_outliers: outside(1.5) - severe_outliers,
+            severe_outliers,
+        }
+    }
+
+    pub fn outliers(&self) -> usize {
+        self.mild_outliers + self.severe_outliers
+    }
+}
+
+/// Format a number of nanoseconds with a sensible unit
+pub fn fmt_ns(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{ns:.2}ns")
+    } else if ns < 1e6 {
+        format!("{:.2}µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2}ms", ns / 1e6)
+    } else {
+        format!("{:.2}s", ns / 1e9)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {} ± {}, min {}, median {}, p95 {}, p99 {} ({} samples x {})",
+            fmt_ns(self.mean),
+            fmt_ns(self.stddev),
+            fmt_ns(self.min),
+            fmt_ns(self.median),
+            fmt_ns(self.p95),
+            fmt_ns(self.p99),
+            self.samples,
+            self.batch,
+        )?;
+        if self.outliers() > 0 {
+            write!(
+                f,
+                "\n  {} outliers ({:.1}%): {} mild, {} severe",
+                self.outliers(),
+                100.0 * self.outliers() as f64 / self.samples as f64,
+                self.mild_outliers,
+                self.severe_outliers
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn timeit<F, U>(f: F, config: &BenchConfig) -> (Stats, U)
+where
+    F: Fn() -> U,
+{
+    // run a few times to get an estimate of timing, without spending too
+    // much of the budget on slow solvers
+    let now = Instant::now();
+    let mut warmups = 0;
+    while warmups < WARMUP_ITERATIONS && (warmups == 0 || now.elapsed() < config.budget / 10) {
+        std::hint::black_box(f());
+        warmups += 1;
+    }
+    // a solver can be fast enough for this to round down to 0
+    let estimate = (now.elapsed() / warmups).max(Duration::from_nanos(1));
+
+    let batch =
+        (MIN_SAMPLE_TIME.as_nanos() / estimate.as_nanos()).clamp(1, u32::MAX as u128) as u32;
+    let samples = config.iterations.unwrap_or_else(|| {
+        let per_sample = estimate.as_nanos() * batch as u128;
+        ((config.budget.as_nanos() / per_sample) as usize).clamp(MIN_SAMPLES, MAX_SAMPLES)
+    });
+
+    let mut times = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let now = Instant::now();
+        for _ in 0..batch {
+            std::hint::black_box(f());
+        }
+        times.push(now.elapsed().as_nanos() as f64 / batch as f64);
+    }
+    let ret = std::hint::black_box(f());
+    (Stats::new(times, batch), ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_stats() {
+        // 1 to 20 in some order, with a mild and a severe outlier
+        let mut samples: Vec<f64> = (1..=20).rev().map(f64::from).collect();
+        samples.extend([100.0, 40.0]);
+        let stats = Stats::new(samples, 3);
+        assert_eq!((stats.samples, stats.batch), (22, 3));
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 11.0);
+        assert_eq!(stats.p95, 40.0);
+        assert_eq!(stats.p99, 100.0);
+        assert!((stats.mean - 350.0 / 22.0).abs() < 1e-9);
+        assert_eq!((stats.mild_outliers, stats.severe_outliers), (1, 1));
+
+        let stats = Stats::new(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], 1);
+        assert_eq!(stats.mean, 5.0);
+        assert!((stats.stddev - (32.0f64 / 7.0).sqrt()).abs() < 1e-9);
+        assert_eq!(Stats::new(vec![8.0], 1).stddev, 0.0);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::generate::Generator;
+use crate::solution::Runner;
+use crate::CALENDARS;
+
+/// One year's puzzles, as found by the build script in `src/yYYYY/dayN.rs`
+pub struct Calendar {
+    pub year: u16,
+    // in order, with gaps for days that haven't been written yet
+    pub days: &'static [&'static dyn Runner],
+    pub generators: &'static [(u8, Generator)],
+}
+
+impl Calendar {
+    pub fn find(year: u16) -> Option<&'static Calendar> {
+        CALENDARS.iter().find(|c| c.year == year)
+    }
+
+    /// The most recent year
+    pub fn latest_year() -> &'static Calendar {
+        CALENDARS.last().expect("no years to solve")
+    }
+
+    pub fn get(&self, day: usize) -> Option<&'static dyn Runner> {
+        self.days.iter().copied().find(|r| r.day() as usize == day)
+    }
+
+    /// The last day with a solution
+    pub fn latest(&self) -> usize {
+        self.days.last().map_or(0, |r| r.day() as usize)
+    }
+
+    pub fn generator(&self, day: usize) -> Option<Generator> {
+        let generator = self.generators.iter().find(|&&(d, _)| d as usize == day);
+        generator.map(|&(_, generator)| generator)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::scaffold;
+use crate::solution::Runner;
+use crate::watchdog;
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Known answers keyed by (day, part)
+pub type Answers = FxHashMap<(u8, u8), String>;
+
+pub fn default_answers_path(year: u16) -> PathBuf {
+    scaffold::answers_path(Path::new(env!("CARGO_MANIFEST_DIR")), year)
+}
+
+/// Parse an answers file made of `day part value` lines
+///
+/// Blank lines and lines starting with `#` are ignored.
+pub fn parse_answers(src: &str) -> Result<Answers, String> {
+    let mut answers = Answers::default();
+    for (i, line) in src.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, char::is_whitespace);
+        let (Some(day), Some(part), Some(value)) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(format!("line {}: expected `day part value`", i + 1));
+        };
+        let day = day
+            .parse()
+            .map_err(|_| format!("line {}: bad day '{day}'", i + 1))?;
+        let part = match part {
+            "1" => 1,
+            "2" => 2,
+            _ => return Err(format!("line {}: bad part '{part}'", i + 1)),
+        };
+        answers.insert((day, part), value.trim().to_owned());
+    }
+    Ok(answers)
+}
+
+/// How many answers matched and how many didn't
+#[derive(Default)]
+pub struct Tally {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// Solve `input` and print a row per part comparing against `expected`,
+/// giving up after `timeout`
+///
+/// Each row starts with `prefix`, so callers can add their own columns.
+pub fn check_input<'a>(
+    prefix: &str,
+    runner: &'static dyn Runner,
+    input: &str,
+    expected: impl Fn(u8) -> Option<&'a str>,
+    timeout: Option<Duration>,
+    tally: &mut Tally,
+) {
+    // keep going if a solver panics so the rest of the table still gets checked
+    let result = watchdog::solve(runner, input, None, timeout, &mut |part, answer| {
+        let actual = answer.to_string();
+        let expected = expected(part);
+        let status = match expected {
+            Some(expected) if expected == actual => {
+                tally.passed += 1;
+                "ok"
+            }
+            Some(_) => {
+                tally.failed += 1;
+                "FAIL"
+            }
+            // nothing to compare against
+            None => "unknown",
+        };
+        println!(
+            "{prefix}  {part:>4}  {:<20}  {actual:<20}  {status}",
+            expected.unwrap_or("?")
+        );
+    });
+    if let Err(e) = result {
+        println!("{prefix}  {:>4}  {e}", "-");
+        tally.failed += 1;
+    }
+}
+
+/// Run every day in `days` and compare against `answers`, printing a table
+///
+/// Returns `true` if nothing failed.
+pub fn check(
+    days: &[&'static dyn Runner],
+    answers: &Answers,
+    timeout: Option<Duration>,
+    read_input: impl Fn(usize) -> Result<String, String>,
+) -> bool {
+    let mut tally = Tally::default();
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:<20}  result",
+        "day", "part", "expected", "actual"
+    );
+    for &runner in days {
+        let day = runner.day() as usize;
+        let input = match read_input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{day:>3}  {:>4}  {e}", "-");
+                tally.failed += 1;
+                continue;
+            }
+        };
+        let expected = |part| answers.get(&(day as u8, part)).map(String::as_str);
+        check_input(
+            &format!("{day:>3}"),
+            runner,
+            &input,
+            expected,
+            timeout,
+            &mut tally,
+        );
+    }
+    println!("\n{} passed, {} failed", tally.passed, tally.failed);
+    tally.failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_file() {
+        let src = "# day part value\n\n1 1 54632\n 1 2  54019 \n24 2 -3\n";
+        let answers = parse_answers(src).unwrap();
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&(1, 1)], "54632");
+        assert_eq!(answers[&(1, 2)], "54019");
+        assert_eq!(answers[&(24, 2)], "-3");
+        assert_eq!(
+            parse_answers("1 1 5\n1 3 6").unwrap_err(),
+            "line 2: bad part '3'"
+        );
+        assert_eq!(parse_answers("x 1 5").unwrap_err(), "line 1: bad day 'x'");
+        assert_eq!(
+            parse_answers("\n2 1").unwrap_err(),
+            "line 2: expected `day part value`"
+        );
+    }
+}
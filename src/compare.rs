@@ -0,0 +1,64 @@
+use crate::bench::{self, fmt_ns, BenchConfig, Stats};
+use crate::solution::{ParseError, Runner};
+
+// One implementation's answer to one part, and how long it took
+struct Row {
+    part: u8,
+    name: &'static str,
+    answer: String,
+    stats: Stats,
+}
+
+/// Time every implementation of `runner` on `input` and print them side by
+/// side, part by part, each against the first
+///
+/// Returns `true` if they all gave the same answers.
+pub fn compare(
+    runner: &dyn Runner,
+    input: &str,
+    part: Option<u8>,
+    config: &BenchConfig,
+) -> Result<bool, ParseError> {
+    let mut rows = Vec::new();
+    for name in runner.implementations() {
+        runner.run_impl(name, input, &mut |n, f| {
+            if part.is_some_and(|p| p != n) {
+                return;
+            }
+            let (stats, answer) = bench::timeit(f, config);
+            rows.push(Row {
+                part: n,
+                name,
+                answer: answer.to_string(),
+                stats,
+            });
+        })?;
+    }
+    rows.sort_by_key(|row| row.part);
+
+    let mut agree = true;
+    println!(
+        "{:>4}  {:<16}  {:<20}  {:>10}  {:>8}",
+        "part", "impl", "answer", "mean", "speedup"
+    );
+    for rows in rows.chunk_by(|a, b| a.part == b.part) {
+        let first = &rows[0];
+        for row in rows {
+            let status = if row.answer == first.answer {
+                ""
+            } else {
+                agree = false;
+                "  DISAGREES"
+            };
+            println!(
+                "{:>4}  {:<16}  {:<20}  {:>10}  {:>7.2}x{status}",
+                row.part,
+                row.name,
+                row.answer,
+                fmt_ns(row.stats.mean),
+                first.stats.mean / row.stats.mean,
+            );
+        }
+    }
+    Ok(agree)
+}
@@ -0,0 +1,328 @@
+use crate::alloc::AllocStats;
+use crate::bench::{fmt_ns, Stats};
+use rustc_hash::FxHashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::process::Command;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// What was being timed
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Step {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "parse" => Some(Step::Parse),
+            "1" => Some(Step::Part(1)),
+            "2" => Some(Step::Part(2)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => f.pad("parse"),
+            Step::Part(n) => f.pad(&n.to_string()),
+        }
+    }
+}
+
+pub struct Record {
+    pub day: usize,
+    pub step: Step,
+    pub stats: Stats,
+    // only counted with the `count-alloc` feature
+    pub allocs: Option<AllocStats>,
+}
+
+/// The revision of the tree the benchmarks were built from, or `unknown`
+/// outside of a git checkout
+pub fn git_revision() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map_or_else(|| "unknown".to_string(), |rev| rev.trim().to_string())
+}
+
+const CSV_HEADER: &str = "revision,day,part,samples,batch,iterations,\
+    mean_ns,stddev_ns,min_ns,median_ns,p95_ns,p99_ns,outliers,allocs,alloc_bytes,peak_bytes";
+
+pub fn write_csv(out: &mut impl Write, revision: &str, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "{CSV_HEADER}")?;
+    for Record {
+        day,
+        step,
+        stats,
+        allocs,
+    } in records
+    {
+        // left empty when allocations weren't counted
+        let allocs = allocs.map_or(",,".to_string(), |a| {
+            format!("{},{},{}", a.allocs, a.bytes, a.peak)
+        });
+        writeln!(
+            out,
+            "{revision},{day},{step},{},{},{},{},{},{},{},{},{},{},{allocs}",
+            stats.samples,
+            stats.batch,
+            stats.samples as u64 * stats.batch as u64,
+            stats.mean,
+            stats.stddev,
+            stats.min,
+            stats.median,
+            stats.p95,
+            stats.p99,
+            stats.outliers(),
+        )?;
+    }
+    Ok(())
+}
+
+// One flat object per line, so `load_baseline` doesn't need a real JSON parser
+pub fn write_json(out: &mut impl Write, revision: &str, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "[")?;
+    for (
+        i,
+        Record {
+            day,
+            step,
+            stats,
+            allocs,
+        },
+    ) in records.iter().enumerate()
+    {
+        let sep = if i + 1 < records.len() { "," } else { "" };
+        let allocs = allocs.map_or(String::new(), |a| {
+            format!(
+                ", \"allocs\": {}, \"alloc_bytes\": {}, \"peak_bytes\": {}",
+                a.allocs, a.bytes, a.peak
+            )
+        });
+        writeln!(
+            out,
+            "  {{\"revision\": \"{revision}\", \"day\": {day}, \"part\": \"{step}\", \
+            \"samples\": {}, \"batch\": {}, \"iterations\": {}, \"mean_ns\": {}, \
+            \"stddev_ns\": {}, \"min_ns\": {}, \"median_ns\": {}, \
+            \"p95_ns\": {}, \"p99_ns\": {}, \"outliers\": {}{allocs}}}{sep}",
+            stats.samples,
+            stats.batch,
+            stats.samples as u64 * stats.batch as u64,
+            stats.mean,
+            stats.stddev,
+            stats.min,
+            stats.median,
+            stats.p95,
+            stats.p99,
+            stats.outliers(),
+        )?;
+    }
+    writeln!(out, "]")
+}
+
+/// Mean times from a previous run, keyed by day and step
+pub type Baseline = FxHashMap<(usize, Step), f64>;
+
+/// Load a run saved with `--format json` or `--format csv`
+pub fn load_baseline(src: &str) -> Result<Baseline, String> {
+    let rows: Vec<FxHashMap<&str, &str>> = if src.trim_start().starts_with('[') {
+        src.split('{')
+            .skip(1)
+            .map(|obj| {
+                let obj = obj.split('}').next().unwrap_or_default();
+                obj.split(',')
+                    .filter_map(|field| field.split_once(':'))
+                    .map(|(k, v)| (k.trim().trim_matches('"'), v.trim().trim_matches('"')))
+                    .collect()
+            })
+            .collect()
+    } else {
+        let mut lines = src.lines();
+        let header: Vec<_> = lines.next().unwrap_or_default().split(',').collect();
+        lines
+            .filter(|line| !line.is_empty())
+            .map(|line| header.iter().copied().zip(line.split(',')).collect())
+            .collect()
+    };
+
+    let mut baseline = Baseline::default();
+    for (i, row) in rows.iter().enumerate() {
+        let field = |name| {
+            row.get(name)
+                .ok_or_else(|| format!("record {}: missing `{name}`", i + 1))
+        };
+        let day = field("day")?;
+        let day = day
+            .parse()
+            .map_err(|_| format!("record {}: bad day '{day}'", i + 1))?;
+        let step = field("part")?;
+        let step =
+            Step::parse(step).ok_or_else(|| format!("record {}: bad part '{step}'", i + 1))?;
+        let mean = field("mean_ns")?;
+        let mean = mean
+            .parse()
+            .map_err(|_| format!("record {}: bad mean '{mean}'", i + 1))?;
+        baseline.insert((day, step), mean);
+    }
+    Ok(baseline)
+}
+
+/// Print how each record compares to `baseline`, flagging anything that got
+/// more than `threshold` percent slower
+///
+/// Returns the number of regressions.
+pub fn compare(
+    out: &mut impl Write,
+    baseline: &Baseline,
+    records: &[Record],
+    threshold: f64,
+) -> io::Result<usize> {
+    let mut regressions = 0;
+    writeln!(
+        out,
+        "{:>3}  {:>5}  {:>10}  {:>10}  {:>8}",
+        "day", "part", "baseline", "now", "change"
+    )?;
+    for Record {
+        day, step, stats, ..
+    } in records
+    {
+        let Some(&before) = baseline.get(&(*day, *step)) else {
+            writeln!(
+                out,
+                "{day:>3}  {step:>5}  {:>10}  {:>10}",
+                "-",
+                fmt_ns(stats.mean)
+            )?;
+            continue;
+        };
+        // a step too quick to register in the baseline has no meaningful change
+        let change = if before == 0.0 {
+            format!("{:>8}", "n/a")
+        } else {
+            let change = 100.0 * (stats.mean - before) / before;
+            let flag = if change > threshold {
+                regressions += 1;
+                "  SLOWER"
+            } else {
+                ""
+            };
+            format!("{change:>+7.1}%{flag}")
+        };
+        writeln!(
+            out,
+            "{day:>3}  {step:>5}  {:>10}  {:>10}  {change}",
+            fmt_ns(before),
+            fmt_ns(stats.mean),
+        )?;
+    }
+    writeln!(
+        out,
+        "\n{regressions} regression(s) over {threshold}% against the baseline"
+    )?;
+    Ok(regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: usize, step: Step, mean: f64, allocs: Option<AllocStats>) -> Record {
+        Record {
+            day,
+            step,
+            stats: Stats {
+                samples: 10,
+                batch: 2,
+                min: mean / 2.0,
+                median: mean,
+                mean,
+                stddev: 1.5,
+                p95: mean * 2.0,
+                p99: mean * 3.0,
+                mild_outliers: 1,
+                severe_outliers: 0,
+            },
+            allocs,
+        }
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let allocs = AllocStats {
+            allocs: 3,
+            bytes: 96,
+            peak: 64,
+        };
+        let records = [
+            record(1, Step::Parse, 12.5, None),
+            record(1, Step::Part(1), 1234.25, Some(allocs)),
+            record(25, Step::Part(2), 1.0 / 3.0, None),
+        ];
+        for write in [write_json, write_csv] {
+            let mut out = Vec::new();
+            write(&mut out, "abc123", &records).unwrap();
+            let baseline = load_baseline(std::str::from_utf8(&out).unwrap()).unwrap();
+            assert_eq!(baseline.len(), 3);
+            assert_eq!(baseline[&(1, Step::Parse)], 12.5);
+            assert_eq!(baseline[&(1, Step::Part(1))], 1234.25);
+            assert_eq!(baseline[&(25, Step::Part(2))], 1.0 / 3.0);
+        }
+    }
+
+    #[test]
+    fn compare_to_zero() {
+        let baseline = Baseline::from_iter([((1, Step::Parse), 0.0), ((1, Step::Part(1)), 10.0)]);
+        let records = [
+            record(1, Step::Parse, 5.0, None),
+            record(1, Step::Part(1), 20.0, None),
+        ];
+        let mut out = Vec::new();
+        let regressions = compare(&mut out, &baseline, &records, 10.0).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert!(lines[1].ends_with("     n/a"), "{out}");
+        assert!(lines[2].ends_with("+100.0%  SLOWER"), "{out}");
+        assert_eq!(regressions, 1);
+    }
+
+    #[test]
+    fn bad_baseline() {
+        let src = "day,part,mean_ns\n3,parse,10\n4,3,10";
+        assert_eq!(
+            load_baseline(src).err().as_deref(),
+            Some("record 2: bad part '3'")
+        );
+        let src = "[\n  {\"day\": 3, \"part\": \"1\"}\n]";
+        assert_eq!(
+            load_baseline(src).err().as_deref(),
+            Some("record 1: missing `mean_ns`")
+        );
+    }
+}
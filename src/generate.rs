@@ -0,0 +1,114 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Builds an input of roughly `scale` times the size of a real one
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// Generate an input with `generator`, always the same one for a given seed
+/// and scale
+pub fn generate(generator: Generator, seed: u64, scale: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    generator(&mut rng, scale.max(1))
+}
+
+// Side length for a square grid with `scale` times the area of a `base` sided one
+pub(crate) fn side(base: usize, scale: usize) -> usize {
+    (base as f64 * (scale as f64).sqrt()).round() as usize
+}
+
+// A `width` by `height` grid with each cell picked by `cell`
+pub(crate) fn grid(
+    rng: &mut StdRng,
+    width: usize,
+    height: usize,
+    mut cell: impl FnMut(&mut StdRng, usize, usize) -> u8,
+) -> Vec<Vec<u8>> {
+    (0..height)
+        .map(|y| (0..width).map(|x| cell(rng, x, y)).collect())
+        .collect()
+}
+
+pub(crate) fn join_rows(rows: &[Vec<u8>]) -> String {
+    let rows: Vec<_> = rows
+        .iter()
+        .map(|row| String::from_utf8_lossy(row))
+        .collect();
+    rows.join("\n")
+}
+
+// The outline of a random shape made of `cols` columns of cells, each one
+// unbroken and overlapping its neighbours, so the outline never touches
+// itself. Returned as the corners it passes through one unit step at a time,
+// going clockwise, with every point inside `0..=cols` by `0..=rows`.
+pub(crate) fn outline(rng: &mut StdRng, cols: usize, rows: usize) -> Vec<(usize, usize)> {
+    assert!(cols >= 1 && rows >= 2);
+    let mut spans: Vec<(usize, usize)> = Vec::with_capacity(cols);
+    for c in 0..cols {
+        let (top, bottom) = match spans.last() {
+            None => {
+                let top = rng.gen_range(0..rows - 1);
+                (top, rng.gen_range(top + 1..=rows))
+            }
+            Some(&(prev_top, prev_bottom)) => {
+                let drift = |rng: &mut StdRng, n: usize| {
+                    (n as isize + rng.gen_range(-3..=3)).clamp(0, rows as isize) as usize
+                };
+                // overlap the previous column by at least one cell
+                let top = drift(rng, prev_top).min(prev_bottom - 1);
+                let bottom = drift(rng, prev_bottom).max(prev_top + 1).max(top + 1);
+                (top, bottom)
+            }
+        };
+        debug_assert!(top < bottom && bottom <= rows, "column {c}");
+        spans.push((top, bottom));
+    }
+
+    let mut points = Vec::new();
+    let walk = |points: &mut Vec<(usize, usize)>, to: (usize, usize)| {
+        let step = |from: usize, to: usize| from + usize::from(from < to) - usize::from(from > to);
+        let &(mut x, mut y) = points.last().unwrap();
+        while (x, y) != to {
+            (x, y) = (step(x, to.0), step(y, to.1));
+            points.push((x, y));
+        }
+    };
+    points.push((0, spans[0].0));
+    // along the tops, left to right
+    for (c, &(top, _)) in spans.iter().enumerate() {
+        walk(&mut points, (c, top));
+        walk(&mut points, (c + 1, top));
+    }
+    // then the bottoms, right to left
+    for (c, &(_, bottom)) in spans.iter().enumerate().rev() {
+        walk(&mut points, (c + 1, bottom));
+        walk(&mut points, (c, bottom));
+    }
+    walk(&mut points, (0, spans[0].0));
+    // the walk finishes where it started
+    points.pop();
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CALENDARS;
+
+    #[test]
+    fn generated_inputs_solve() {
+        // a few days take seconds unoptimized, so run them side by side
+        std::thread::scope(|scope| {
+            for calendar in CALENDARS {
+                for &(day, generator) in calendar.generators {
+                    let runner = calendar.get(day as usize).unwrap();
+                    scope.spawn(move || {
+                        let input = generate(generator, 0, 1);
+                        runner
+                            .run(&input, &mut |_, solve| drop(solve()))
+                            .unwrap_or_else(|e| panic!("{} {e}", calendar.year));
+                    });
+                }
+            }
+        });
+    }
+}
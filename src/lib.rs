@@ -0,0 +1,34 @@
+//! Solutions to Advent of Code, one `yYYYY` module per year
+//!
+//! Each `dayN` module has `part1` and `part2`, along with a `DayN` type
+//! implementing [`solution::Solution`] for the harness. Days whose parts take
+//! a parsed type (like day 20's `Circuit`) also have a `parse` function
+//! building it; the rest work on the raw input, so `<DayN as
+//! Solution>::parse` only checks it's well formed.
+//! The build script finds them in `src/yYYYY/dayN.rs` and lists them in
+//! [`CALENDARS`].
+
+pub mod alloc;
+pub mod answer;
+pub mod batch;
+pub mod bench;
+pub mod calendar;
+pub mod check;
+pub mod compare;
+pub mod export;
+pub mod generate;
+pub mod log;
+#[cfg(test)]
+mod reference;
+pub mod scaffold;
+pub mod solution;
+pub mod stepper;
+pub mod summary;
+pub mod visualize;
+pub mod watchdog;
+
+pub use answer::Answer;
+pub use calendar::Calendar;
+pub use solution::{ParseError, Solution};
+
+include!(concat!(env!("OUT_DIR"), "/calendars.rs"));
@@ -0,0 +1,208 @@
+use std::cell::Cell;
+use std::fmt::{Arguments, Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// How much detail a message goes into, from least to most
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// The most detailed level that gets compiled in at all
+///
+/// Debug and trace messages sit in hot loops, so release builds leave them
+/// out unless the `trace-log` feature is on.
+pub const STATIC_MAX: Level = if cfg!(any(debug_assertions, feature = "trace-log")) {
+    Level::Trace
+} else {
+    Level::Info
+};
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown log level '{s}'")),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        })
+    }
+}
+
+/// Which messages to show, parsed from a `RUST_LOG` style list like
+/// `info,day23=trace`
+///
+/// A bare level applies everywhere, and `module=level` to any module with
+/// that in its path, like `day23` or `y2023::day23`, and anything inside it.
+/// The last matching entry wins.
+#[derive(Clone, Debug)]
+pub struct Filter {
+    // `None` for the default, otherwise part of a module path
+    directives: Vec<(Option<String>, Option<Level>)>,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::level(Some(Level::Warn))
+    }
+}
+
+impl Filter {
+    /// Show everything up to `level` everywhere, or nothing at all
+    pub fn level(level: Option<Level>) -> Self {
+        Self {
+            directives: vec![(None, level)],
+        }
+    }
+
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (module, level) = match directive.split_once('=') {
+                Some((module, level)) => (Some(module.to_string()), level),
+                None => (None, directive),
+            };
+            let level = match level {
+                "off" => None,
+                level => Some(level.parse()?),
+            };
+            filter.directives.push((module, level));
+        }
+        Ok(filter)
+    }
+
+    // The most detailed level shown anywhere, for a quick check before
+    // looking at the module
+    fn max(&self) -> u8 {
+        let levels = self
+            .directives
+            .iter()
+            .map(|(_, level)| level.map_or(0, |l| l as u8));
+        levels.max().unwrap_or(0)
+    }
+
+    pub fn enabled(&self, level: Level, module: &str) -> bool {
+        // whole path segments only, so `day2` doesn't match `day23`
+        let module = format!("::{module}::");
+        let matches = |target: &str| module.contains(&format!("::{target}::"));
+        self.directives
+            .iter()
+            .rev()
+            .find(|(target, _)| target.as_deref().is_none_or(matches))
+            .and_then(|&(_, max)| max)
+            .is_some_and(|max| level <= max)
+    }
+}
+
+static MAX: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+
+thread_local! {
+    // the day being solved on this thread, to label its messages
+    static DAY: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+/// Replace the filter deciding which messages get shown
+pub fn set_filter(filter: Filter) {
+    MAX.store(filter.max(), Ordering::Relaxed);
+    *FILTER.write().unwrap() = Some(filter);
+}
+
+/// Whether anything at `level` could be shown, without checking the module
+#[inline(always)]
+pub fn max_enabled(level: Level) -> bool {
+    level <= STATIC_MAX && level as u8 <= MAX.load(Ordering::Relaxed)
+}
+
+pub fn enabled(level: Level, module: &str) -> bool {
+    let filter = FILTER.read().unwrap();
+    match &*filter {
+        Some(filter) => filter.enabled(level, module),
+        None => Filter::default().enabled(level, module),
+    }
+}
+
+/// Label messages logged on this thread with `day`
+pub fn set_day(day: u8) {
+    DAY.set(Some(day));
+}
+
+/// Write a message to stderr, for use through [`log!`](crate::log!)
+pub fn write(level: Level, args: Arguments) {
+    match DAY.get() {
+        Some(day) => eprintln!("[{level:<5} day {day}] {args}"),
+        None => eprintln!("[{level:<5}] {args}"),
+    }
+}
+
+/// Log a message at `level` to stderr, if the filter lets it through
+///
+/// The message is only formatted when it's going to be shown.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {{
+        let level = $level;
+        if $crate::log::max_enabled(level) && $crate::log::enabled(level, module_path!()) {
+            $crate::log::write(level, format_args!($($arg)*));
+        }
+    }};
+}
+
+/// Progress from a slow solver, shown with `-v`
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+/// Step by step detail from inside hot loops
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Trace, $($arg)*) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_filters() {
+        let filter = Filter::parse("info,day23=trace,day2=off").unwrap();
+        assert!(filter.enabled(Level::Info, "advent_of_code::y2023::day5"));
+        assert!(!filter.enabled(Level::Debug, "advent_of_code::y2023::day5"));
+        assert!(filter.enabled(Level::Trace, "advent_of_code::y2023::day23"));
+        assert!(filter.enabled(Level::Trace, "advent_of_code::y2023::day23::graph"));
+        assert!(!filter.enabled(Level::Error, "advent_of_code::y2023::day2"));
+        assert!(filter.enabled(Level::Info, "advent_of_code::y2023::day22"));
+        let filter = Filter::parse("y2023::day5=debug").unwrap();
+        assert!(filter.enabled(Level::Debug, "advent_of_code::y2023::day5"));
+        assert!(!filter.enabled(Level::Debug, "advent_of_code::y2024::day5"));
+        assert!(Filter::parse("day5=loud").is_err());
+    }
+}
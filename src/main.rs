@@ -1,53 +1,699 @@
-use seq_macro::seq;
+use advent_of_code::alloc::{self, AllocStats};
+use advent_of_code::bench::{self, fmt_ns, BenchConfig};
+use advent_of_code::export::{self, Format, Record, Step};
+use advent_of_code::generate::{self, Generator};
+use advent_of_code::log::{self, Filter, Level};
+use advent_of_code::solution::{self, ParseError, Runner, WithImpl};
+use advent_of_code::stepper::Stepper;
+use advent_of_code::visualize::{self, FrameWriter};
+use advent_of_code::{batch, check, compare, scaffold, summary, watchdog, Calendar};
+use std::io::Read;
+use std::ops::RangeInclusive;
+use std::path::Path;
 use std::time::{Duration, Instant};
-seq!(N in 1..=25 {
-    #[allow(unused)]
-    mod day~N;
-});
 
-fn timeit<F, U>(f: F) -> (Duration, U)
-where
-    F: Fn() -> U,
-{
-    // run a few times to get an estimate of timing
-    let now = Instant::now();
-    for _ in 0..32 {
-        std::hint::black_box(f());
+const USAGE: &str = "\
+usage: advent-of-code [DAYS] [--year YEAR] [--part 1|2] [--bench] [--check]
+                     [--impl NAME] [--compare]
+                     [--input PATH] [--inputs-dir DIR] [--timeout SECS] [-v]
+                     [--log SPEC] [--visualize DIR [--frames FMT]] [--step]
+       advent-of-code generate DAY [--year YEAR] [--seed S] [--scale K]
+       advent-of-code new YEAR DAY
+
+DAYS is a single day (`17`), `all`, or a range (`3..=9`, `3..10`).
+Defaults to the latest day, every day with --check or --inputs-dir, or every
+day with more than one implementation with --compare. Running more than one
+day prints a table of answers and times.
+
+    --year YEAR     the year to solve, defaulting to the latest one
+    --part N        only run part N
+    --bench         time each part instead of running it once; builds with
+                    the `count-alloc` feature also count allocations
+    --iterations N  take N timing samples per part with --bench
+    --budget SECS   time to spend on each part with --bench (default 5)
+    --format FMT    print --bench results as `text` (default), `json` or `csv`
+    --baseline PATH compare --bench results against a run saved with
+                    --format json or csv
+    --threshold PCT how much slower than the baseline counts as a
+                    regression (default 10)
+    --jobs N        solve up to N days at once when running several days,
+                    which slows each down, so only the wall clock is a fair
+                    time; --bench always runs one thing at a time
+    --impl NAME     solve a single day with its implementation called NAME
+                    rather than the usual one
+    --compare       time every implementation of each day, using the same
+                    --iterations and --budget as --bench, and exit with an
+                    error if they don't all agree
+    --check         compare each answer against src/answers/YEAR.txt and
+                    exit with an error if any are wrong
+    --input PATH    read the puzzle input from PATH (`-` for stdin) instead
+                    of src/inputs/YEAR/N.txt; only valid for a single day
+    --inputs-dir DIR
+                    run each day against every DIR/N*.txt (`21.txt`,
+                    `21-alice.txt`, ...) and compare against the answers in
+                    a matching `.answers` file where there is one
+    --timeout SECS  give up on a day once it's spent SECS parsing and
+                    solving, rather than waiting for it forever; doesn't
+                    apply to --bench
+    -v, --verbose   log progress from slow days to stderr; `-vv` and `-vvv`
+                    go into more detail, in debug builds or with the
+                    `trace-log` feature
+    --log SPEC      choose what to log like RUST_LOG, e.g.
+                    `info,day22=trace`; otherwise taken from RUST_LOG
+    --visualize DIR save pictures of days that draw their progress (10, 14,
+                    16, 17, 18, 21 and 23) to DIR, one file per frame plus a
+                    `-strip` of each sequence; doesn't apply to --bench
+    --frames FMT    save --visualize frames as `png` (default), `ppm` or
+                    `svg`
+    --step          step through the same pictures in the terminal, along
+                    with each step of the simulations in days 14, 16, 20 and
+                    22. Commands are read a line at a time, so each one
+                    needs enter; type `?` at the prompt to list them
+
+`generate` prints a random input for DAY, the same one every time for a
+given seed (default 0). --scale makes it about K times the size of a real
+input, where the puzzle allows (default 1).
+
+`new` starts a day with a template in src/yYEAR/dayN.rs and an empty input
+to paste into. The next build picks it up.";
+
+struct Args {
+    year: u16,
+    days: Vec<&'static dyn Runner>,
+    part: Option<u8>,
+    bench: bool,
+    bench_config: BenchConfig,
+    format: Format,
+    baseline: Option<String>,
+    threshold: f64,
+    check: bool,
+    input: Option<String>,
+    inputs_dir: Option<String>,
+    timeout: Option<Duration>,
+    log: Option<Filter>,
+    jobs: usize,
+    visualize: Option<(String, visualize::Format)>,
+    step: bool,
+    compare: bool,
+}
+
+fn parse_year(s: &str) -> Result<&'static Calendar, String> {
+    s.parse()
+        .ok()
+        .and_then(Calendar::find)
+        .ok_or_else(|| format!("no solutions for year '{s}'"))
+}
+
+fn parse_day(s: &str, calendar: &Calendar) -> Result<usize, String> {
+    match s.parse() {
+        Ok(day @ 1..) if day <= calendar.latest() => Ok(day),
+        _ => Err(format!("no such day '{s}'")),
     }
-    let initial_avg = now.elapsed() / 32;
+}
 
-    let measure_loops = (Duration::from_secs(5).as_nanos() / initial_avg.as_nanos()) as u32;
+fn parse_days(s: &str, calendar: &Calendar) -> Result<RangeInclusive<usize>, String> {
+    let days = if s == "all" {
+        1..=calendar.latest()
+    } else if let Some((lo, hi)) = s.split_once("..=") {
+        parse_day(lo, calendar)?..=parse_day(hi, calendar)?
+    } else if let Some((lo, hi)) = s.split_once("..") {
+        // exclusive end, so `1..26` is the whole calendar
+        let lo = parse_day(lo, calendar)?;
+        match hi.parse::<usize>() {
+            Ok(hi) if hi <= lo => return Err(format!("empty range '{s}'")),
+            Ok(hi) => lo..=parse_day(&(hi - 1).to_string(), calendar)?,
+            Err(_) => return Err(format!("no such day '{hi}'")),
+        }
+    } else {
+        let day = parse_day(s, calendar)?;
+        if calendar.get(day).is_none() {
+            return Err(format!("no solution for day {day} of {}", calendar.year));
+        }
+        day..=day
+    };
+    if days.is_empty() {
+        return Err(format!("empty range '{s}'"));
+    }
+    Ok(days)
+}
 
-    let now = Instant::now();
-    for _ in 0..measure_loops {
-        std::hint::black_box(f());
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut days = None;
+    let mut year = None;
+    let mut part = None;
+    let mut bench = false;
+    let mut bench_config = BenchConfig::default();
+    let mut format = None;
+    let mut baseline = None;
+    let mut threshold = None;
+    let mut check = false;
+    let mut input = None;
+    let mut inputs_dir = None;
+    let mut timeout = None;
+    let mut verbosity = 0;
+    let mut log = None;
+    let mut jobs = 1;
+    let mut visualize = None;
+    let mut frames = None;
+    let mut step = false;
+    let mut implementation = None;
+    let mut compare = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            "--bench" => bench = true,
+            "--check" => check = true,
+            "--step" => step = true,
+            "--compare" => compare = true,
+            "--impl" => implementation = Some(args.next().ok_or("--impl needs a value")?),
+            "--verbose" => verbosity += 1,
+            // `-v`, `-vv` or `-vvv`
+            flag if flag.len() > 1 && flag[1..].bytes().all(|b| b == b'v') => {
+                verbosity += flag.len() - 1
+            }
+            "--log" => {
+                let spec = args.next().ok_or("--log needs a value")?;
+                log = Some(Filter::parse(&spec)?);
+            }
+            "--iterations" => {
+                let n = args.next().ok_or("--iterations needs a value")?;
+                match n.parse() {
+                    Ok(n @ 1..) => bench_config.iterations = Some(n),
+                    _ => return Err(format!("invalid iteration count '{n}'")),
+                }
+            }
+            "--budget" => {
+                let secs = args.next().ok_or("--budget needs a value")?;
+                bench_config.budget = secs
+                    .parse()
+                    .ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .ok_or_else(|| format!("invalid budget '{secs}'"))?;
+            }
+            "--format" => {
+                let f = args.next().ok_or("--format needs a value")?;
+                format = Some(Format::parse(&f).ok_or_else(|| format!("unknown format '{f}'"))?);
+            }
+            "--timeout" => {
+                let secs = args.next().ok_or("--timeout needs a value")?;
+                timeout = Some(
+                    secs.parse()
+                        .ok()
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                        .ok_or_else(|| format!("invalid timeout '{secs}'"))?,
+                );
+            }
+            "--baseline" => baseline = Some(args.next().ok_or("--baseline needs a value")?),
+            "--threshold" => {
+                let pct = args.next().ok_or("--threshold needs a value")?;
+                match pct.parse() {
+                    Ok(pct @ 0.0..) => threshold = Some(pct),
+                    _ => return Err(format!("invalid threshold '{pct}'")),
+                }
+            }
+            "--part" => {
+                let n = args.next().ok_or("--part needs a value")?;
+                part = match n.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("no such part '{n}'")),
+                };
+            }
+            "--jobs" => {
+                let n = args.next().ok_or("--jobs needs a value")?;
+                match n.parse() {
+                    Ok(n @ 1..) => jobs = n,
+                    _ => return Err(format!("invalid job count '{n}'")),
+                }
+            }
+            "--year" => year = Some(parse_year(&args.next().ok_or("--year needs a value")?)?),
+            "--input" => input = Some(args.next().ok_or("--input needs a value")?),
+            "--visualize" => visualize = Some(args.next().ok_or("--visualize needs a value")?),
+            "--frames" => {
+                let f = args.next().ok_or("--frames needs a value")?;
+                frames = Some(
+                    visualize::Format::parse(&f)
+                        .ok_or_else(|| format!("unknown frame format '{f}'"))?,
+                );
+            }
+            "--inputs-dir" => inputs_dir = Some(args.next().ok_or("--inputs-dir needs a value")?),
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            _ if days.is_some() => return Err(format!("unexpected argument '{arg}'")),
+            _ => days = Some(arg),
+        }
+    }
+    let calendar = year.unwrap_or_else(Calendar::latest_year);
+    let days = match days {
+        Some(days) => parse_days(&days, calendar)?,
+        None if check || inputs_dir.is_some() || compare => 1..=calendar.latest(),
+        None => calendar.latest()..=calendar.latest(),
+    };
+    // skipping any days in the range that haven't been written yet
+    let mut days: Vec<_> = days.filter_map(|day| calendar.get(day)).collect();
+    if compare && days.len() > 1 {
+        // nothing to compare a lone implementation against
+        days.retain(|runner| runner.implementations().len() > 1);
+        if days.is_empty() {
+            return Err("none of those days have more than one implementation".to_string());
+        }
     }
-    let avg = now.elapsed() / measure_loops;
-    let ret = std::hint::black_box(f());
-    (avg, ret)
+    if compare && (bench || check || inputs_dir.is_some() || implementation.is_some()) {
+        return Err(
+            "--compare can't be combined with --bench, --check, --inputs-dir or --impl".to_string(),
+        );
+    }
+    if compare && (timeout.is_some() || visualize.is_some() || step) {
+        return Err(
+            "--compare can't be combined with --timeout, --visualize or --step".to_string(),
+        );
+    }
+    if let Some(name) = implementation {
+        let [runner] = days[..] else {
+            return Err("--impl can only be used with a single day".to_string());
+        };
+        let implementations = runner.implementations();
+        let Some(&name) = implementations.iter().find(|&&i| i == name) else {
+            return Err(format!(
+                "day {} has no implementation '{name}', only {}",
+                runner.day(),
+                implementations.join(", ")
+            ));
+        };
+        // leaked to be `'static` like the rest of the days; it's only the one
+        days = vec![Box::leak(Box::new(WithImpl { runner, name }))];
+    }
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    if inputs_dir.is_some() && (bench || check || input.is_some()) {
+        return Err("--inputs-dir can't be combined with --bench, --check or --input".to_string());
+    }
+    if bench && timeout.is_some() {
+        return Err("--timeout can't be combined with --bench".to_string());
+    }
+    if bench && visualize.is_some() {
+        return Err("--visualize can't be combined with --bench".to_string());
+    }
+    if step && (visualize.is_some() || bench || timeout.is_some() || jobs > 1) {
+        return Err(
+            "--step can't be combined with --visualize, --bench, --timeout or --jobs".to_string(),
+        );
+    }
+    if step && input.as_deref() == Some("-") {
+        return Err(
+            "--step reads commands from stdin, so can't read the input from it".to_string(),
+        );
+    }
+    if frames.is_some() && visualize.is_none() {
+        return Err("--frames only applies to --visualize".to_string());
+    }
+    if !bench && (format.is_some() || baseline.is_some() || threshold.is_some()) {
+        return Err("--format, --baseline and --threshold only apply to --bench".to_string());
+    }
+    Ok(Args {
+        year: calendar.year,
+        days,
+        part,
+        bench,
+        bench_config,
+        format: format.unwrap_or(Format::Text),
+        baseline,
+        threshold: threshold.unwrap_or(10.0),
+        check,
+        input,
+        inputs_dir,
+        timeout,
+        log: log.or_else(|| {
+            let level = [Level::Info, Level::Debug, Level::Trace];
+            (verbosity > 0).then(|| Filter::level(Some(level[verbosity.min(3) - 1])))
+        }),
+        jobs,
+        visualize: visualize.map(|dir| (dir, frames.unwrap_or(visualize::Format::Png))),
+        step,
+        compare,
+    })
 }
 
-type AocFn = fn() -> usize;
+// The generator for the chosen day, and the seed and scale to use it with
+fn parse_generate_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(Generator, u64, usize), String> {
+    let mut day = None;
+    let mut year = None;
+    let mut seed = 0;
+    let mut scale = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(parse_year(&args.next().ok_or("--year needs a value")?)?),
+            "--seed" => {
+                let s = args.next().ok_or("--seed needs a value")?;
+                seed = s.parse().map_err(|_| format!("invalid seed '{s}'"))?;
+            }
+            "--scale" => {
+                let k = args.next().ok_or("--scale needs a value")?;
+                match k.parse() {
+                    Ok(k @ 1..) => scale = k,
+                    _ => return Err(format!("invalid scale '{k}'")),
+                }
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            _ if day.is_some() => return Err(format!("unexpected argument '{arg}'")),
+            _ => day = Some(arg),
+        }
+    }
+    let calendar = year.unwrap_or_else(Calendar::latest_year);
+    let day = parse_day(&day.ok_or("generate needs a day")?, calendar)?;
+    let generator = calendar
+        .generator(day)
+        .ok_or_else(|| format!("no generator for day {day} of {}", calendar.year))?;
+    Ok((generator, seed, scale))
+}
 
-seq! {
-    N in 1..=25 {
-        static FUNCS: &[(AocFn, AocFn)] = &[
-            #(
-                (day~N::part1 as _, day~N::part2 as _),
-            )*
-        ];
+// The year and day to start with `new`
+fn parse_new_args(mut args: impl Iterator<Item = String>) -> Result<(u16, u8), String> {
+    let year = args.next().ok_or("new needs a year and a day")?;
+    let year = match year.parse() {
+        Ok(year @ 2015..) => year,
+        _ => return Err(format!("invalid year '{year}'")),
+    };
+    let day = args.next().ok_or("new needs a year and a day")?;
+    let day = match day.parse() {
+        Ok(day @ 1..=25) => day,
+        _ => return Err(format!("no such day '{day}'")),
+    };
+    if let Some(arg) = args.next() {
+        return Err(format!("unexpected argument '{arg}'"));
     }
+    Ok((year, day))
 }
 
-fn main() {
-    let (f1, f2) = FUNCS.last().unwrap();
-    if std::env::var("TIMEIT").is_ok() {
-        let (t1, res) = timeit(f1);
-        println!("Solved part 1 in {t1:?} - {res}");
-        let (t2, res) = timeit(f2);
-        println!("Solved part 2 in {t2:?} - {res}");
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn read_input(year: u16, day: usize, path: Option<&str>) -> Result<String, String> {
+    let input = match path {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("couldn't read stdin: {e}"))?;
+            Ok(input)
+        }
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|e| format!("couldn't read {path}: {e}"))
+        }
+        None => {
+            let mut path = scaffold::input_path(manifest_dir(), year, day as u8);
+            if let Some(old) = scaffold::legacy_input_path(manifest_dir(), year, day as u8) {
+                eprintln!(
+                    "warning: reading {}, which should be moved to {}",
+                    old.display(),
+                    path.display()
+                );
+                path = old;
+            }
+            std::fs::read_to_string(&path)
+                .map_err(|e| format!("couldn't read {}: {e}", path.display()))
+        }
+    }?;
+    Ok(solution::normalize_input(input))
+}
+
+// counting again outside `timeit`, so the bookkeeping doesn't skew the times
+fn count_allocs<U>(f: impl FnOnce() -> U) -> Option<AllocStats> {
+    alloc::enabled().then(|| alloc::measure(f).0)
+}
+
+fn bench_day(
+    day: usize,
+    runner: &dyn Runner,
+    input: &str,
+    args: &Args,
+) -> Result<Vec<Record>, ParseError> {
+    let text = args.format == Format::Text;
+    let mut records = Vec::new();
+    let (stats, parsed) = bench::timeit(|| runner.parse_only(input), &args.bench_config);
+    parsed?;
+    let allocs = count_allocs(|| runner.parse_only(input));
+    if text {
+        println!("Parsed in {}", fmt_ns(stats.mean));
+        println!("  {stats}");
+        if let Some(allocs) = allocs {
+            println!("  {allocs}");
+        }
+    }
+    records.push(Record {
+        day,
+        step: Step::Parse,
+        stats,
+        allocs,
+    });
+    runner.run(input, &mut |n, f| {
+        if args.part.is_some_and(|p| p != n) {
+            return;
+        }
+        let (stats, res) = bench::timeit(f, &args.bench_config);
+        let allocs = count_allocs(f);
+        if text {
+            println!("Solved part {n} in {} - {res}", fmt_ns(stats.mean));
+            println!("  {stats}");
+            if let Some(allocs) = allocs {
+                println!("  {allocs}");
+            }
+        }
+        records.push(Record {
+            day,
+            step: Step::Part(n),
+            stats,
+            allocs,
+        });
+    })?;
+    Ok(records)
+}
+
+// One row per day with the mean time taken to parse and to solve each part
+fn print_bench_table(records: &[Record]) {
+    println!(
+        "\n{:>3}  {:>10}  {:>10}  {:>10}",
+        "day", "parse", "part 1", "part 2"
+    );
+    for day_records in records.chunk_by(|a, b| a.day == b.day) {
+        let time = |step| {
+            day_records
+                .iter()
+                .find(|r| r.step == step)
+                .map_or("-".to_string(), |r| fmt_ns(r.stats.mean))
+        };
+        println!(
+            "{:>3}  {:>10}  {:>10}  {:>10}",
+            day_records[0].day,
+            time(Step::Parse),
+            time(Step::Part(1)),
+            time(Step::Part(2))
+        );
+    }
+}
+
+fn report_bench(records: &[Record], args: &Args) -> std::io::Result<bool> {
+    let revision = export::git_revision();
+    match args.format {
+        Format::Text => print_bench_table(records),
+        Format::Json => export::write_json(&mut std::io::stdout(), &revision, records)?,
+        Format::Csv => export::write_csv(&mut std::io::stdout(), &revision, records)?,
+    }
+    let Some(path) = &args.baseline else {
+        return Ok(true);
+    };
+    let baseline = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|src| export::load_baseline(&src));
+    let baseline = match baseline {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("error: couldn't load baseline {path}: {e}");
+            return Ok(false);
+        }
+    };
+    // keep stdout machine-readable
+    let regressions = if args.format == Format::Text {
+        println!();
+        export::compare(&mut std::io::stdout(), &baseline, records, args.threshold)?
     } else {
-        println!("Part 1 - {}", f1());
-        println!("Part 2 - {}", f2());
+        export::compare(&mut std::io::stderr(), &baseline, records, args.threshold)?
+    };
+    Ok(regressions == 0)
+}
+
+// Finish off any pictures before going
+fn exit(ok: bool) -> ! {
+    visualize::finish();
+    std::process::exit(if ok { 0 } else { 1 })
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.next_if(|arg| arg == "generate").is_some() {
+        match parse_generate_args(args) {
+            Ok((generator, seed, scale)) => {
+                println!("{}", generate::generate(generator, seed, scale))
+            }
+            Err(e) => {
+                eprintln!("error: {e}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+    if args.next_if(|arg| arg == "new").is_some() {
+        let created = parse_new_args(args)
+            .map_err(|e| format!("{e}\n\n{USAGE}"))
+            .and_then(|(year, day)| scaffold::new_day(manifest_dir(), year, day));
+        match created {
+            Ok(created) => {
+                for path in created {
+                    println!("created {}", path.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    let filter = match args.log.clone() {
+        Some(filter) => Ok(filter),
+        None => std::env::var("RUST_LOG").map_or(Ok(Filter::default()), |spec| {
+            Filter::parse(&spec).map_err(|e| format!("RUST_LOG: {e}"))
+        }),
+    };
+    match filter {
+        Ok(filter) => log::set_filter(filter),
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(2);
+        }
+    }
+    if let Some((dir, format)) = &args.visualize {
+        match FrameWriter::new(Path::new(dir), *format) {
+            Ok(writer) => visualize::set_sink(Box::new(writer)),
+            Err(e) => {
+                eprintln!("error: couldn't create {dir}: {e}");
+                std::process::exit(2);
+            }
+        }
+    }
+    if args.step {
+        visualize::set_sink(Box::new(Stepper::terminal()));
+    }
+    if args.check {
+        let path = check::default_answers_path(args.year);
+        let answers = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|src| check::parse_answers(&src));
+        let answers = match answers {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("error: couldn't load {}: {e}", path.display());
+                std::process::exit(1);
+            }
+        };
+        let ok = check::check(&args.days, &answers, args.timeout, |day| {
+            read_input(args.year, day, args.input.as_deref())
+        });
+        exit(ok);
+    }
+    if let Some(dir) = &args.inputs_dir {
+        let ok = batch::check_dir(&args.days, Path::new(dir), args.timeout);
+        exit(ok);
+    }
+    if args.compare {
+        let mut ok = true;
+        for &runner in &args.days {
+            let day = runner.day() as usize;
+            println!("Day {day}: {}", runner.title());
+            let compared = read_input(args.year, day, args.input.as_deref()).and_then(|input| {
+                compare::compare(runner, &input, args.part, &args.bench_config)
+                    .map_err(|e| format!("couldn't parse input: {e}"))
+            });
+            match compared {
+                Ok(agree) => ok &= agree,
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    ok = false;
+                }
+            }
+            println!();
+        }
+        exit(ok);
+    }
+    let multiple = args.days.len() > 1;
+    if multiple && !args.bench {
+        let now = Instant::now();
+        let summaries = summary::solve_all(&args.days, args.part, args.timeout, args.jobs, |day| {
+            read_input(args.year, day, args.input.as_deref())
+        });
+        let ok = summary::print_table(&summaries, args.jobs);
+        if args.jobs > 1 {
+            println!(
+                "wall clock {} with {} jobs",
+                fmt_ns(now.elapsed().as_nanos() as f64),
+                args.jobs
+            );
+        }
+        exit(ok);
+    }
+    let mut records = Vec::new();
+    for &runner in &args.days {
+        let day = runner.day() as usize;
+        if multiple && args.format == Format::Text {
+            println!("Day {day}: {}", runner.title());
+        }
+        let input = match read_input(args.year, day, args.input.as_deref()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        };
+        if args.bench {
+            match bench_day(day, runner, &input, &args) {
+                Ok(r) => records.extend(r),
+                Err(e) => {
+                    eprintln!("error: couldn't parse input: {e}");
+                    std::process::exit(1);
+                }
+            }
+            continue;
+        }
+        let result = watchdog::solve(runner, &input, args.part, args.timeout, &mut |n, answer| {
+            println!("Part {n} - {answer}");
+        });
+        if let Err(e) = result {
+            eprintln!("error: {e}");
+            exit(false);
+        }
+    }
+    visualize::finish();
+    if args.bench {
+        match report_bench(&records, &args) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,443 @@
+// Slow but obviously correct solutions for the days that rely on shortcuts,
+// checked against the real ones on lots of small random inputs, plus days
+// with more than one implementation checked against themselves
+
+use crate::generate;
+use crate::solution::{solve, Runner, Solution};
+#[cfg(feature = "z3")]
+use crate::y2023::day24;
+use crate::y2023::{day11, day18, day21, day23, day25, day5, day6};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rustc_hash::FxHashMap;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+// Map every seed one at a time
+fn day5(input: &str, ranges: bool) -> usize {
+    let mut sections = input.split("\n\n");
+    let numbers: Vec<usize> = sections.next().unwrap()["seeds: ".len()..]
+        .split(' ')
+        .map(|n| n.parse().unwrap())
+        .collect();
+    let seeds: Vec<usize> = if ranges {
+        numbers
+            .chunks(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1])
+            .collect()
+    } else {
+        numbers
+    };
+    let maps: Vec<Vec<[usize; 3]>> = sections
+        .map(|section| {
+            let lines = section.lines().skip(1);
+            let line = lines.map(|l| l.split(' ').map(|n| n.parse().unwrap()));
+            line.map(|mut n| [(); 3].map(|_| n.next().unwrap()))
+                .collect()
+        })
+        .collect();
+    let location = |mut n: usize| {
+        for map in &maps {
+            if let Some([dest, src, _]) = map
+                .iter()
+                .find(|[_, src, len]| (*src..src + len).contains(&n))
+            {
+                n = n - src + dest;
+            }
+        }
+        n
+    };
+    seeds.into_iter().map(location).min().unwrap()
+}
+
+// Try holding the button for every possible number of milliseconds
+fn day6(input: &str, joined: bool) -> usize {
+    let numbers = |line: &str| -> Vec<u64> {
+        let (_, numbers) = line.split_once(':').unwrap();
+        if joined {
+            vec![numbers.replace(' ', "").parse().unwrap()]
+        } else {
+            numbers
+                .split_whitespace()
+                .map(|n| n.parse().unwrap())
+                .collect()
+        }
+    };
+    let (times, distances) = input.split_once('\n').unwrap();
+    let wins = |(time, distance): (u64, u64)| {
+        (0..=time)
+            .filter(|held| held * (time - held) > distance)
+            .count()
+    };
+    numbers(times)
+        .into_iter()
+        .zip(numbers(distances))
+        .map(wins)
+        .product()
+}
+
+// Actually insert the extra rows and columns
+fn day11(input: &str, growth: usize) -> usize {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let empty_row = |y: usize| !rows[y].contains(&b'#');
+    let empty_col = |x: usize| rows.iter().all(|row| row[x] != b'#');
+    let mut expanded: Vec<Vec<u8>> = Vec::new();
+    for (y, src) in rows.iter().enumerate() {
+        let mut row = Vec::new();
+        for (x, &cell) in src.iter().enumerate() {
+            let copies = if empty_col(x) { growth } else { 1 };
+            row.extend(std::iter::repeat_n(cell, copies));
+        }
+        let copies = if empty_row(y) { growth } else { 1 };
+        expanded.extend(std::iter::repeat_n(row, copies));
+    }
+    let galaxies: Vec<(usize, usize)> = expanded
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            let xs = row.iter().enumerate().filter(|&(_, &b)| b == b'#');
+            xs.map(move |(x, _)| (x, y))
+        })
+        .collect();
+    let mut sum = 0;
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[i + 1..] {
+            sum += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+        }
+    }
+    sum
+}
+
+// Dig out the trench on a grid, then flood fill the outside
+fn day18(input: &str, hex: bool) -> usize {
+    let steps: Vec<(u8, i64)> = input
+        .lines()
+        .map(|line| {
+            let mut parts = line.split(' ');
+            let (dir, len) = (parts.next().unwrap(), parts.next().unwrap());
+            let colour = &parts.next().unwrap()[2..8];
+            if hex {
+                let dir = b"RDLU"[usize::from(colour.as_bytes()[5] - b'0')];
+                (dir, i64::from_str_radix(&colour[..5], 16).unwrap())
+            } else {
+                (dir.as_bytes()[0], len.parse().unwrap())
+            }
+        })
+        .collect();
+    let mut dug: Vec<(i64, i64)> = vec![(0, 0)];
+    for (dir, len) in steps {
+        let (dx, dy) = match dir {
+            b'R' => (1, 0),
+            b'D' => (0, 1),
+            b'L' => (-1, 0),
+            _ => (0, -1),
+        };
+        for _ in 0..len {
+            let &(x, y) = dug.last().unwrap();
+            dug.push((x + dx, y + dy));
+        }
+    }
+    // leave a border of outside around the trench to fill through
+    let min_x = dug.iter().map(|p| p.0).min().unwrap() - 1;
+    let min_y = dug.iter().map(|p| p.1).min().unwrap() - 1;
+    let width = (dug.iter().map(|p| p.0).max().unwrap() - min_x + 2) as usize;
+    let height = (dug.iter().map(|p| p.1).max().unwrap() - min_y + 2) as usize;
+    let mut trench = vec![vec![false; width]; height];
+    for (x, y) in dug {
+        trench[(y - min_y) as usize][(x - min_x) as usize] = true;
+    }
+    let mut outside = vec![vec![false; width]; height];
+    outside[0][0] = true;
+    let mut visit: Vec<(usize, usize)> = vec![(0, 0)];
+    while let Some((x, y)) = visit.pop() {
+        let next = [
+            (x + 1, y),
+            (x, y + 1),
+            (x.wrapping_sub(1), y),
+            (x, y.wrapping_sub(1)),
+        ];
+        for (nx, ny) in next {
+            if nx < width && ny < height && !trench[ny][nx] && !outside[ny][nx] {
+                outside[ny][nx] = true;
+                visit.push((nx, ny));
+            }
+        }
+    }
+    width * height - outside.iter().flatten().filter(|&&out| out).count()
+}
+
+// Walk out from the start over copies of the garden in every direction
+fn day21(input: &str, steps: u32) -> usize {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let size = rows.len() as i64;
+    let rock =
+        |x: i64, y: i64| rows[y.rem_euclid(size) as usize][x.rem_euclid(size) as usize] == b'#';
+    let start = rows
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| Some((row.iter().position(|&b| b == b'S')? as i64, y as i64)))
+        .unwrap();
+    let mut distances = FxHashMap::default();
+    distances.insert(start, 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        let distance = distances[&(x, y)];
+        if distance == steps {
+            continue;
+        }
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if !rock(next.0, next.1) && !distances.contains_key(&next) {
+                distances.insert(next, distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances.values().filter(|&&d| d % 2 == steps % 2).count()
+}
+
+// A handful of small seed ranges, and maps of non-overlapping chunks of a
+// small range, which often start or end right next to each other
+fn small_day5(rng: &mut StdRng) -> String {
+    const MAX: usize = 100;
+    let seeds: Vec<String> = (0..rng.gen_range(1..=4))
+        .map(|_| format!("{} {}", rng.gen_range(0..MAX), rng.gen_range(1..=20)))
+        .collect();
+    let mut out = format!("seeds: {}", seeds.join(" "));
+    for name in day5::MAPS {
+        out.push_str(&format!("\n\n{name} map:"));
+        let mut src = rng.gen_range(0..10);
+        while src < MAX {
+            let len = rng.gen_range(1..=20);
+            if rng.gen_bool(0.6) {
+                out.push_str(&format!("\n{} {src} {len}", rng.gen_range(0..MAX)));
+            }
+            src += len + rng.gen_range(0..=2);
+        }
+    }
+    out
+}
+
+// Up to three races that can all be won, some of them only just
+fn small_day6(rng: &mut StdRng) -> String {
+    let races: Vec<(u64, u64)> = (0..rng.gen_range(1..=3))
+        .map(|_| {
+            let time = rng.gen_range(2..60);
+            (time, rng.gen_range(0..time * time / 4))
+        })
+        .collect();
+    let width = 2 + races
+        .iter()
+        .map(|(_, d)| d.to_string().len())
+        .max()
+        .unwrap();
+    let mut time = "Time:    ".to_string();
+    let mut distance = "Distance:".to_string();
+    for (t, d) in races {
+        time.push_str(&format!("{t:>width$}"));
+        distance.push_str(&format!("{d:>width$}"));
+    }
+    format!("{time}\n{distance}")
+}
+
+// A small grid of galaxies, with plenty of empty rows and columns
+fn small_day11(rng: &mut StdRng) -> String {
+    let (width, height) = (rng.gen_range(1..12), rng.gen_range(2..12));
+    let mut rows = generate::grid(rng, width, height, |rng, _, _| {
+        if rng.gen_bool(0.15) {
+            b'#'
+        } else {
+            b'.'
+        }
+    });
+    rows[rng.gen_range(0..height)][rng.gen_range(0..width)] = b'#';
+    generate::join_rows(&rows)
+}
+
+// The outline of a small blob, with each unit of it stretched a short way in
+// both parts
+fn small_day18(rng: &mut StdRng) -> String {
+    let size = rng.gen_range(2..6);
+    let outline = generate::outline(rng, size, size);
+    let mut gaps = |max: i64| -> Vec<i64> { (0..size).map(|_| rng.gen_range(1..=max)).collect() };
+    let (short_x, short_y) = (gaps(9), gaps(9));
+    let (long_x, long_y) = (gaps(0x1f), gaps(0x1f));
+    let lines: Vec<String> = (0..outline.len())
+        .map(|i| {
+            let (x, y) = outline[i];
+            let (nx, ny) = outline[(i + 1) % outline.len()];
+            let (dir, short, hex_dir, long) = match (nx.cmp(&x), ny.cmp(&y)) {
+                (Ordering::Greater, _) => ('R', short_x[x], 0, long_x[x]),
+                (Ordering::Less, _) => ('L', short_x[nx], 2, long_x[nx]),
+                (_, Ordering::Greater) => ('D', short_y[y], 1, long_y[y]),
+                _ => ('U', short_y[ny], 3, long_y[ny]),
+            };
+            format!("{dir} {short} (#{long:05x}{hex_dir})")
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// Two small cliques joined by three wires, so the only way to cut the graph in
+// three cuts is between them
+fn small_day25(rng: &mut StdRng) -> String {
+    let (left, right) = (rng.gen_range(5..=12), rng.gen_range(5..=12));
+    // at most 24 of them
+    let name = |n: usize| format!("aa{}", (b'a' + n as u8) as char);
+    // each wired to the rest of its clique that comes after it
+    let mut wires: Vec<Vec<usize>> = (0..left + right)
+        .map(|a| (a + 1..if a < left { left } else { left + right }).collect())
+        .collect();
+    let mut cut = 0;
+    while cut < 3 {
+        let (a, b) = (rng.gen_range(0..left), rng.gen_range(left..left + right));
+        if !wires[a].contains(&b) {
+            wires[a].push(b);
+            cut += 1;
+        }
+    }
+    let lines: Vec<String> = wires
+        .iter()
+        .enumerate()
+        .filter(|(_, to)| !to.is_empty())
+        .map(|(from, to)| {
+            let to: Vec<String> = to.iter().map(|&n| name(n)).collect();
+            format!("{}: {}", name(from), to.join(" "))
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// Compare a day's answer for `part` with the reference one on inputs made by
+// `gen` from a run of seeds, listing every input they disagree on
+fn differential<S: Solution>(
+    part: u8,
+    gen: fn(&mut StdRng) -> String,
+    reference: impl Fn(&str) -> usize,
+) {
+    let mut disagreements = Vec::new();
+    for seed in 0..300 {
+        let input = gen(&mut StdRng::seed_from_u64(seed));
+        let actual = solve::<S>(&input, part);
+        let expected = reference(&input).to_string();
+        if actual != expected {
+            disagreements.push(format!(
+                "seed {seed}: expected {expected}, got {actual} for\n{input}"
+            ));
+        }
+    }
+    assert!(
+        disagreements.is_empty(),
+        "day {} part {part} disagrees with the reference on {} inputs\n\n{}",
+        S::DAY,
+        disagreements.len(),
+        disagreements.join("\n\n")
+    );
+}
+
+#[test]
+fn day5_part1() {
+    differential::<day5::Day5>(1, small_day5, |input| day5(input, false));
+}
+
+#[test]
+fn day5_part2() {
+    differential::<day5::Day5>(2, small_day5, |input| day5(input, true));
+}
+
+#[test]
+fn day6_part1() {
+    differential::<day6::Day6>(1, small_day6, |input| day6(input, false));
+}
+
+#[test]
+fn day6_part2() {
+    differential::<day6::Day6>(2, small_day6, |input| day6(input, true));
+}
+
+#[test]
+fn day11_part1() {
+    differential::<day11::Day11>(1, small_day11, |input| day11(input, 2));
+}
+
+// Part 2's growth is too much to insert, so try a smaller one directly
+#[test]
+fn day11_growth() {
+    for seed in 0..300 {
+        let input = small_day11(&mut StdRng::seed_from_u64(seed));
+        let space = day11::parse(&input).unwrap();
+        assert_eq!(space.path_count::<10>(), day11(&input, 10), "\n{input}");
+    }
+}
+
+#[test]
+fn day18_part1() {
+    differential::<day18::Day18>(1, small_day18, |input| day18(input, false));
+}
+
+#[test]
+fn day18_part2() {
+    differential::<day18::Day18>(2, small_day18, |input| day18(input, true));
+}
+
+// Part 2 walks 202300 gardens out, so check its formula a few gardens out
+#[test]
+fn day21_tiled() {
+    for seed in 0..3 {
+        let input = day21::generate(&mut StdRng::seed_from_u64(seed), 1);
+        for n in [2, 4] {
+            let steps = 65 + 131 * n as u32;
+            assert_eq!(
+                day21::count_tiled(&input, n),
+                day21(&input, steps),
+                "seed {seed}, {steps} steps"
+            );
+        }
+    }
+}
+
+// Check every other implementation of a day against the first on inputs made
+// by `gen`
+fn implementations_agree(runner: &dyn Runner, part: u8, gen: fn(&mut StdRng) -> String) {
+    let implementations = runner.implementations();
+    for seed in 0..20 {
+        let input = gen(&mut StdRng::seed_from_u64(seed));
+        let answers: Vec<String> = implementations
+            .iter()
+            .map(|name| {
+                let mut answer = String::new();
+                runner
+                    .run_impl(name, &input, &mut |n, f| {
+                        if n == part {
+                            answer = f().to_string();
+                        }
+                    })
+                    .unwrap();
+                answer
+            })
+            .collect();
+        assert!(
+            answers.iter().all(|a| *a == answers[0]),
+            "day {} part {part} seed {seed}: {implementations:?} gave {answers:?}",
+            runner.day()
+        );
+    }
+}
+
+// the brute force part 2 is too slow to run this many times
+#[test]
+fn day23_implementations() {
+    implementations_agree(&day23::Day23, 1, |rng| day23::generate(rng, 1));
+}
+
+#[test]
+fn day25_implementations() {
+    implementations_agree(&day25::Day25, 1, small_day25);
+}
+
+// there's only the native solver to compare without z3
+#[cfg(feature = "z3")]
+#[test]
+fn day24_implementations() {
+    implementations_agree(&day24::Day24, 1, |rng| day24::generate(rng, 1));
+    implementations_agree(&day24::Day24, 2, |rng| day24::generate(rng, 1));
+}
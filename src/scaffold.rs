@@ -0,0 +1,150 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+// `{day}` gets replaced with the day number
+const TEMPLATE: &str = r#"use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+    const TITLE: &'static str = "";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input)
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Answer {
+    Answer::Nothing
+}
+
+pub fn part2(input: &str) -> Answer {
+    Answer::Nothing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "no example yet"]
+    fn part1_example() {
+        assert_eq!(solve::<Day{day}>(EXAMPLE, 1), "");
+    }
+
+    #[test]
+    #[ignore = "no example yet"]
+    fn part2_example() {
+        assert_eq!(solve::<Day{day}>(EXAMPLE, 2), "");
+    }
+}
+"#;
+
+pub fn module_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(format!("src/y{year}/day{day}.rs"))
+}
+
+pub fn input_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(format!("src/inputs/{year}/{day}.txt"))
+}
+
+/// Where a 2023 input still lives if it was saved before inputs were split up
+/// by year, as `src/inputs/N.txt`, and hasn't been moved to [`input_path`]
+pub fn legacy_input_path(root: &Path, year: u16, day: u8) -> Option<PathBuf> {
+    if year != 2023 || input_path(root, year, day).exists() {
+        return None;
+    }
+    Some(root.join(format!("src/inputs/{day}.txt"))).filter(|path| path.exists())
+}
+
+pub fn answers_path(root: &Path, year: u16) -> PathBuf {
+    root.join(format!("src/answers/{year}.txt"))
+}
+
+// Create `path` with `contents`, unless it's already there
+fn create(path: &Path, contents: &str) -> io::Result<bool> {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    match std::fs::File::create_new(path) {
+        Ok(mut file) => file.write_all(contents.as_bytes()).map(|()| true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Start a new day in the crate at `root`: a module for the build script to
+/// pick up, an empty input, and the year's answers file if it's the first day
+///
+/// Refuses to replace a module that's already there. Returns the files it
+/// created.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, String> {
+    let module = module_path(root, year, day);
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+    let answers = format!(
+        "# Known answers for the inputs in src/inputs/{year}/, checked with `--check`\n\
+         # One per line: day part value\n"
+    );
+    let files = [
+        (module, TEMPLATE.replace("{day}", &day.to_string())),
+        (input_path(root, year, day), String::new()),
+        (answers_path(root, year), answers),
+    ];
+    let mut created = Vec::new();
+    for (path, contents) in files {
+        match create(&path, &contents) {
+            Ok(true) => created.push(path),
+            Ok(false) => {}
+            Err(e) => return Err(format!("couldn't write {}: {e}", path.display())),
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_day_keeps_existing_files() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let created = new_day(&root, 2024, 3).unwrap();
+        assert_eq!(created.len(), 3);
+        let module = std::fs::read_to_string(module_path(&root, 2024, 3)).unwrap();
+        assert!(module.contains("impl Solution for Day3 {"));
+
+        // a second day only needs its own files, and the first can't be redone
+        assert_eq!(new_day(&root, 2024, 4).unwrap().len(), 2);
+        assert!(new_day(&root, 2024, 3).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn legacy_inputs() {
+        let root = std::env::temp_dir().join(format!("aoc-legacy-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/inputs")).unwrap();
+        std::fs::write(root.join("src/inputs/5.txt"), "").unwrap();
+        let old = Some(root.join("src/inputs/5.txt"));
+        assert_eq!(legacy_input_path(&root, 2023, 5), old);
+        assert_eq!(legacy_input_path(&root, 2023, 6), None);
+        assert_eq!(legacy_input_path(&root, 2024, 5), None);
+        // once it's been moved the old one is left alone
+        create(&input_path(&root, 2023, 5), "").unwrap();
+        assert_eq!(legacy_input_path(&root, 2023, 5), None);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,357 @@
+use crate::answer::Answer;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Why an input couldn't be parsed, and where
+#[derive(Debug)]
+pub struct ParseError {
+    // filled in by the harness, as parsers don't know which day they're for
+    pub day: u8,
+    // both 1-based
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// An error at the start of `rest`, which must be a slice of `input`
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = rest.as_ptr() as usize - input.as_ptr() as usize;
+        debug_assert!(offset <= input.len(), "`rest` isn't part of `input`");
+        Self::at_offset(input, offset, expected)
+    }
+
+    /// An error `offset` bytes into `input`
+    pub fn at_offset(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            day: 0,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found: describe(&input[offset..]),
+        }
+    }
+}
+
+/// `s`, which must be a slice of `input`, as a number
+pub fn parse_number<T: FromStr>(input: &str, s: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| ParseError::at(input, s, "a number"))
+}
+
+/// Check every row of `grid`, which must be a slice of `input`, is as wide as
+/// the first and made of cells `cell` accepts, returning that width
+///
+/// Grid days index the input by byte offset, so this keeps a ragged or
+/// mistyped row from reading the wrong cell or running off the end.
+pub fn check_grid(
+    input: &str,
+    grid: &str,
+    expected: &str,
+    cell: impl Fn(u8) -> bool,
+) -> Result<usize, ParseError> {
+    let width = grid.find('\n').unwrap_or(grid.len());
+    for line in grid.lines() {
+        if let Some(i) = line.bytes().position(|b| !cell(b)) {
+            let expected = if i < width { expected } else { "end of line" };
+            return Err(ParseError::at(input, &line[i..], expected));
+        }
+        if line.len() < width {
+            let expected = format!("a row {width} wide");
+            return Err(ParseError::at(input, &line[line.len()..], expected));
+        }
+        if line.len() > width {
+            return Err(ParseError::at(input, &line[width..], "end of line"));
+        }
+    }
+    Ok(width)
+}
+
+// Describe whatever starts at `rest` for an error message
+fn describe(rest: &str) -> String {
+    match rest.chars().next() {
+        None => "end of input".to_string(),
+        Some('\r') => "a carriage return (the input has CRLF line endings)".to_string(),
+        Some('\n') => "end of line".to_string(),
+        Some(c) if c.is_whitespace() => format!("{c:?}"),
+        // the whole word, or just the one symbol
+        Some(c) if c.is_alphanumeric() => {
+            let end = rest.find(|c: char| !c.is_alphanumeric());
+            format!("'{}'", &rest[..end.unwrap_or(rest.len())])
+        }
+        Some(c) => format!("'{c}'"),
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Catch the problems every parser would trip over before the day sees them
+fn check_input(input: &str) -> Result<(), ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::at_offset(input, 0, "puzzle input"));
+    }
+    if let Some(i) = input.find('\r') {
+        return Err(ParseError::at_offset(input, i, "'\\n' line endings"));
+    }
+    if input.ends_with('\n') {
+        return Err(ParseError::at_offset(
+            input,
+            input.trim_end_matches('\n').len(),
+            "the input to end without a newline",
+        ));
+    }
+    Ok(())
+}
+
+/// Put an input into the shape every parser expects: `\n` line endings, no
+/// byte order mark and no trailing newline
+///
+/// Inputs saved on Windows or with an editor that adds a final newline then
+/// give the same answers as the originals.
+pub fn normalize_input(mut input: String) -> String {
+    if let Some(rest) = input.strip_prefix('\u{feff}') {
+        input = rest.to_string();
+    }
+    if input.contains('\r') {
+        input = input.replace("\r\n", "\n");
+    }
+    input.truncate(input.trim_end().len());
+    input
+}
+
+/// A solver for a single day of the calendar
+///
+/// The input is parsed once with [`Solution::parse`] and the result is
+/// shared between both parts.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// What to call `part1` and `part2` next to any
+    /// [`alternatives`](Solution::alternatives)
+    const IMPL: &'static str = "default";
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Answer;
+    fn part2(parsed: &Self::Parsed<'_>) -> Answer;
+
+    /// Other ways of solving the same parsed input, for `--impl` and
+    /// `--compare`
+    fn alternatives() -> Vec<Alternative<Self>> {
+        Vec::new()
+    }
+}
+
+/// A solver for one part of a [`Solution`]
+pub type Part<S> = for<'a, 'b> fn(&'b <S as Solution>::Parsed<'a>) -> Answer;
+
+/// Another implementation of both parts of a day
+pub struct Alternative<S: Solution + ?Sized> {
+    pub name: &'static str,
+    pub part1: Part<S>,
+    pub part2: Part<S>,
+}
+
+/// Object-safe view of a [`Solution`] so every day can live in one registry
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    /// Parse `input` and throw the result away, so the parser can be timed
+    /// on its own
+    fn parse_only(&self, input: &str) -> Result<(), ParseError>;
+    /// Parse `input`, then call `f` with the part number and a closure
+    /// solving that part
+    fn run(
+        &self,
+        input: &str,
+        f: &mut dyn FnMut(u8, &dyn Fn() -> Answer),
+    ) -> Result<(), ParseError>;
+    /// The name of every implementation, the one `run` uses first
+    fn implementations(&self) -> Vec<&'static str>;
+    /// Like `run`, with the implementation called `name`
+    ///
+    /// Panics if there's no such implementation.
+    fn run_impl(
+        &self,
+        name: &str,
+        input: &str,
+        f: &mut dyn FnMut(u8, &dyn Fn() -> Answer),
+    ) -> Result<(), ParseError>;
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse_only(&self, input: &str) -> Result<(), ParseError> {
+        check_input(input)
+            .and_then(|()| S::parse(input))
+            .map(|parsed| drop(std::hint::black_box(parsed)))
+            .map_err(|e| ParseError { day: S::DAY, ..e })
+    }
+
+    fn run(
+        &self,
+        input: &str,
+        f: &mut dyn FnMut(u8, &dyn Fn() -> Answer),
+    ) -> Result<(), ParseError> {
+        self.run_impl(S::IMPL, input, f)
+    }
+
+    fn implementations(&self) -> Vec<&'static str> {
+        let alternatives = S::alternatives().into_iter().map(|a| a.name);
+        std::iter::once(S::IMPL).chain(alternatives).collect()
+    }
+
+    fn run_impl(
+        &self,
+        name: &str,
+        input: &str,
+        f: &mut dyn FnMut(u8, &dyn Fn() -> Answer),
+    ) -> Result<(), ParseError> {
+        let (part1, part2): (Part<S>, Part<S>) = if name == S::IMPL {
+            (S::part1, S::part2)
+        } else {
+            let alternative = S::alternatives().into_iter().find(|a| a.name == name);
+            let alternative = alternative
+                .unwrap_or_else(|| panic!("day {} has no implementation '{name}'", S::DAY));
+            (alternative.part1, alternative.part2)
+        };
+        let parsed = check_input(input)
+            .and_then(|()| S::parse(input))
+            .map_err(|e| ParseError { day: S::DAY, ..e })?;
+        f(1, &|| part1(&parsed));
+        f(2, &|| part2(&parsed));
+        Ok(())
+    }
+}
+
+/// A day run with one of its other implementations, so the rest of the
+/// harness can treat it like any other day
+pub struct WithImpl {
+    pub runner: &'static dyn Runner,
+    pub name: &'static str,
+}
+
+impl Runner for WithImpl {
+    fn day(&self) -> u8 {
+        self.runner.day()
+    }
+
+    fn title(&self) -> &'static str {
+        self.runner.title()
+    }
+
+    fn parse_only(&self, input: &str) -> Result<(), ParseError> {
+        self.runner.parse_only(input)
+    }
+
+    fn run(
+        &self,
+        input: &str,
+        f: &mut dyn FnMut(u8, &dyn Fn() -> Answer),
+    ) -> Result<(), ParseError> {
+        self.runner.run_impl(self.name, input, f)
+    }
+
+    fn implementations(&self) -> Vec<&'static str> {
+        self.runner.implementations()
+    }
+
+    fn run_impl(
+        &self,
+        name: &str,
+        input: &str,
+        f: &mut dyn FnMut(u8, &dyn Fn() -> Answer),
+    ) -> Result<(), ParseError> {
+        self.runner.run_impl(name, input, f)
+    }
+}
+
+/// Parse `input` and solve one part of it, for checking puzzle examples
+#[cfg(test)]
+pub fn solve<S: Solution>(input: &str, part: u8) -> String {
+    let parsed = S::parse(input).unwrap();
+    match part {
+        1 => S::part1(&parsed),
+        2 => S::part2(&parsed),
+        _ => unreachable!(),
+    }
+    .to_string()
+}
+
+/// Parse `input`, which has something wrong with it, and say what and where,
+/// for checking parse errors
+#[cfg(test)]
+pub fn parse_error<S: Solution>(input: &str) -> String {
+    match S::parse(input) {
+        Ok(_) => panic!("{input:?} parsed"),
+        Err(e) => ParseError { day: S::DAY, ..e }.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_windows_input() {
+        let input = "\u{feff}#.#\r\n..#\r\n\r\n";
+        assert_eq!(normalize_input(input.to_string()), "#.#\n..#");
+        assert_eq!(normalize_input("#.#\n..#\n".to_string()), "#.#\n..#");
+    }
+
+    #[test]
+    fn error_location() {
+        let input = "ab\ncd,ef";
+        let e = ParseError::at(input, &input[6..], "a letter");
+        assert_eq!((e.line, e.column), (2, 4));
+        assert_eq!(
+            e.to_string(),
+            "day 0, line 2, column 4: expected a letter, found 'ef'"
+        );
+        let e = ParseError::at_offset(input, input.len(), "more");
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 6, "end of input"));
+    }
+
+    #[test]
+    fn crlf_input() {
+        let e = check_input("#.\r\n.#").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(
+            e.found,
+            "a carriage return (the input has CRLF line endings)"
+        );
+    }
+
+    #[test]
+    fn ragged_grid() {
+        let input = "#.#\n..\n#.#";
+        let e = check_grid(input, input, "'.' or '#'", |b| b == b'.' || b == b'#').unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(
+            (e.expected.as_str(), e.found.as_str()),
+            ("a row 3 wide", "end of line")
+        );
+        let input = "#.#\n.x.";
+        let e = check_grid(input, input, "'.' or '#'", |b| b == b'.' || b == b'#').unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "'x'"));
+    }
+}
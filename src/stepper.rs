@@ -0,0 +1,245 @@
+use crate::visualize::{self, Image, Render, Rgb, Sink};
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::sync::Mutex;
+
+// Frames kept to go back through
+const HISTORY: usize = 200;
+
+const PROMPT: &str =
+    "enter: step  n N: step N  b N: back  u TEXT: run until  c: run to the end  q: quit";
+
+const HELP: &str = "\
+enter, n [N]  step forward one frame, or N
+b [N]         go back one frame, or N, to look again
+u TEXT        run until a frame's status or sequence mentions TEXT
+c             run to the end without stopping
+q             quit";
+
+// A frame that's come past, to go back to
+struct Seen {
+    header: String,
+    status: String,
+    image: Image,
+}
+
+struct State {
+    input: Box<dyn BufRead + Send>,
+    output: Box<dyn Write + Send>,
+    history: VecDeque<Seen>,
+    // frames so far in each sequence
+    counts: FxHashMap<String, usize>,
+    // frames to let go past before stopping again
+    skip: usize,
+    until: Option<String>,
+    // stopped stepping, so let everything run
+    detached: bool,
+}
+
+/// Draws each frame in the terminal as it comes and waits to be told to go
+/// on, getting every step of a simulation as well as the usual frames
+///
+/// Commands are read a line at a time rather than as single keypresses, so
+/// the terminal stays in its normal mode and they can be piped in as well.
+/// Simulations can't run backwards, so going back looks through the last few
+/// frames instead.
+pub struct Stepper {
+    state: Mutex<State>,
+}
+
+impl Stepper {
+    pub fn new(input: impl BufRead + Send + 'static, output: impl Write + Send + 'static) -> Self {
+        Self {
+            state: Mutex::new(State {
+                input: Box::new(input),
+                output: Box::new(output),
+                history: VecDeque::new(),
+                counts: FxHashMap::default(),
+                skip: 0,
+                until: None,
+                detached: false,
+            }),
+        }
+    }
+
+    /// Taking commands from stdin and drawing on stderr, so answers on stdout
+    /// stay separate
+    pub fn terminal() -> Self {
+        Self::new(io::BufReader::new(io::stdin()), io::stderr())
+    }
+}
+
+/// `image` in 24-bit ANSI colour, two rows of pixels to a line by drawing
+/// the top one as the foreground of a half block and the bottom one as its
+/// background
+pub fn ansi(image: &Image) -> String {
+    let mut out = String::new();
+    for y in (0..image.height).step_by(2) {
+        let (mut fg, mut bg) = (None, None);
+        for x in 0..image.width {
+            let top = image.pixels[y * image.width + x];
+            let bottom: Option<Rgb> =
+                (y + 1 < image.height).then(|| image.pixels[(y + 1) * image.width + x]);
+            if fg != Some(top) {
+                let [r, g, b] = top;
+                write!(out, "\x1b[38;2;{r};{g};{b}m").unwrap();
+                fg = Some(top);
+            }
+            if bg != Some(bottom) {
+                match bottom {
+                    Some([r, g, b]) => write!(out, "\x1b[48;2;{r};{g};{b}m").unwrap(),
+                    None => out.push_str("\x1b[49m"),
+                }
+                bg = Some(bottom);
+            }
+            out.push('▀');
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+impl State {
+    fn detach(&mut self) {
+        self.detached = true;
+        visualize::stop_stepping();
+    }
+
+    fn draw(&mut self, view: usize, message: &str) {
+        let seen = &self.history[view];
+        let back = self.history.len() - 1 - view;
+        let mut screen = String::from("\x1b[H\x1b[2J");
+        screen.push_str(&ansi(&seen.image));
+        screen.push_str(&seen.header);
+        if back > 0 {
+            write!(screen, " ({back} back)").unwrap();
+        }
+        if !seen.status.is_empty() {
+            write!(screen, " - {}", seen.status).unwrap();
+        }
+        write!(screen, "\n{message}\n> ").unwrap();
+        // nothing to be done if the terminal's gone
+        let _ = self.output.write_all(screen.as_bytes());
+        let _ = self.output.flush();
+    }
+
+    // Show the newest frame and take commands until told to carry on
+    fn browse(&mut self) {
+        let latest = self.history.len() - 1;
+        let mut view = latest;
+        let mut message = PROMPT;
+        loop {
+            self.draw(view, message);
+            message = PROMPT;
+            let mut line = String::new();
+            if !matches!(self.input.read_line(&mut line), Ok(1..)) {
+                // nothing more to read, so let the rest run
+                self.detach();
+                return;
+            }
+            let line = line.trim();
+            let (command, arg) = line.split_once(' ').unwrap_or((line, ""));
+            let n = if arg.is_empty() {
+                Some(1)
+            } else {
+                arg.parse().ok()
+            };
+            match (command, n) {
+                ("" | "n", Some(n)) if view + n <= latest => view += n,
+                ("" | "n", Some(n)) => {
+                    self.skip = n - (latest - view) - 1;
+                    return;
+                }
+                ("b", Some(n)) => view = view.saturating_sub(n),
+                ("u", _) if !arg.is_empty() => {
+                    self.until = Some(arg.to_string());
+                    return;
+                }
+                ("c", _) => {
+                    self.detach();
+                    return;
+                }
+                ("q", _) => std::process::exit(0),
+                _ => message = HELP,
+            }
+        }
+    }
+}
+
+impl Sink for Stepper {
+    fn frame(&self, sequence: &str, frame: &dyn Render) {
+        let mut state = self.state.lock().unwrap();
+        if state.detached {
+            return;
+        }
+        let count = state.counts.entry(sequence.to_string()).or_default();
+        let header = format!("{sequence} frame {count}");
+        *count += 1;
+        let status = frame.status();
+        let stop = match &state.until {
+            _ if state.skip > 0 => false,
+            Some(text) => status.contains(text.as_str()) || sequence.contains(text.as_str()),
+            None => true,
+        };
+        if state.history.len() == HISTORY {
+            state.history.pop_front();
+        }
+        state.history.push_back(Seen {
+            header,
+            status,
+            image: Image::render(frame, 1),
+        });
+        if !stop {
+            state.skip = state.skip.saturating_sub(1);
+            return;
+        }
+        state.until = None;
+        state.browse();
+    }
+
+    fn steps(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::Canvas;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Screen(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Screen {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn step_back_and_run_until() {
+        let commands = "\nn 3\nb 2\nn\nn 2\nu number 8\nc\n";
+        let screen = Screen::default();
+        let stepper = Stepper::new(io::Cursor::new(commands), screen.clone());
+        for i in 0..10 {
+            let mut canvas = Canvas::new(2, 3);
+            canvas.status = format!("number {i}");
+            stepper.frame("test", &canvas);
+        }
+        let screen = String::from_utf8(screen.0.lock().unwrap().clone()).unwrap();
+        let shown: Vec<_> = screen
+            .lines()
+            .filter_map(|line| line.strip_prefix("test frame "))
+            .map(|line| &line[..1])
+            .collect();
+        assert_eq!(shown, ["0", "1", "4", "2", "3", "5", "8"]);
+        assert!(screen.contains("test frame 2 (2 back) - number 2"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::answer::Answer;
+use crate::bench::fmt_ns;
+use crate::solution::Runner;
+use crate::watchdog;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// days taking longer than this get marked in the table
+const SLOW: Duration = Duration::from_secs(1);
+
+/// The outcome of solving one day once
+pub struct DaySummary {
+    pub day: usize,
+    pub result: Result<[Option<Answer>; 2], String>,
+    // parsing plus both parts
+    pub elapsed: Duration,
+}
+
+/// Solve a single day, skipping the other part if `part` is given and giving
+/// up after `timeout`
+pub fn solve(
+    day: usize,
+    runner: &'static dyn Runner,
+    input: &str,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+) -> DaySummary {
+    let mut answers = [None, None];
+    let now = Instant::now();
+    // one broken day shouldn't stop the rest of the table
+    let result = watchdog::solve(runner, input, part, timeout, &mut |n, answer| {
+        answers[n as usize - 1] = Some(answer);
+    });
+    let elapsed = now.elapsed();
+    DaySummary {
+        day,
+        result: result.map(|()| answers).map_err(|e| e.to_string()),
+        elapsed,
+    }
+}
+
+/// Solve every day in `days` on up to `jobs` threads
+///
+/// The summaries come back in day order however the days get scheduled.
+pub fn solve_all(
+    days: &[&'static dyn Runner],
+    part: Option<u8>,
+    timeout: Option<Duration>,
+    jobs: usize,
+    read_input: impl Fn(usize) -> Result<String, String> + Sync,
+) -> Vec<DaySummary> {
+    let next = AtomicUsize::new(0);
+    let summaries = Mutex::new(Vec::with_capacity(days.len()));
+    let worker = || {
+        while let Some(&runner) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+            let day = runner.day() as usize;
+            let summary = match read_input(day) {
+                Ok(input) => solve(day, runner, &input, part, timeout),
+                Err(e) => DaySummary {
+                    day,
+                    result: Err(e),
+                    elapsed: Duration::ZERO,
+                },
+            };
+            summaries.lock().unwrap().push(summary);
+        }
+    };
+    std::thread::scope(|s| {
+        for _ in 1..jobs.min(days.len()) {
+            s.spawn(worker);
+        }
+        worker();
+    });
+    let mut summaries = summaries.into_inner().unwrap();
+    summaries.sort_unstable_by_key(|s| s.day);
+    summaries
+}
+
+/// Print one row per day and the total time taken, solved on `jobs` threads
+///
+/// Returns `true` if every day was solved.
+pub fn print_table(summaries: &[DaySummary], jobs: usize) -> bool {
+    let mut ok = true;
+    let mut total = Duration::ZERO;
+    if jobs > 1 {
+        // a day sharing the machine with others can look slow when it isn't
+        println!(
+            "times overlap, so include waiting on the other {} jobs",
+            jobs - 1
+        );
+    }
+    println!(
+        "{:>3}  {:<20}  {:<20}  {:>10}",
+        "day", "part 1", "part 2", "elapsed"
+    );
+    for DaySummary {
+        day,
+        result,
+        elapsed,
+    } in summaries
+    {
+        total += *elapsed;
+        match result {
+            Ok([part1, part2]) => {
+                let answer =
+                    |a: &Option<Answer>| a.as_ref().map_or("-".to_string(), Answer::to_string);
+                let slow = if *elapsed > SLOW { "  (slow)" } else { "" };
+                println!(
+                    "{day:>3}  {:<20}  {:<20}  {:>10}{slow}",
+                    answer(part1),
+                    answer(part2),
+                    fmt_ns(elapsed.as_nanos() as f64),
+                );
+            }
+            Err(e) => {
+                ok = false;
+                println!("{day:>3}  error: {e}");
+            }
+        }
+    }
+    println!(
+        "\n{} days in {}",
+        summaries.len(),
+        fmt_ns(total.as_nanos() as f64)
+    );
+    ok
+}
@@ -0,0 +1,546 @@
+use crate::log::Level;
+use rustc_hash::FxHashMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0x10, 0x10, 0x18];
+pub const GREY: Rgb = [0x70, 0x70, 0x78];
+pub const WHITE: Rgb = [0xf0, 0xf0, 0xf0];
+pub const RED: Rgb = [0xe0, 0x40, 0x30];
+pub const ORANGE: Rgb = [0xf0, 0x90, 0x20];
+pub const YELLOW: Rgb = [0xf0, 0xe0, 0x40];
+pub const GREEN: Rgb = [0x40, 0xc0, 0x50];
+pub const BLUE: Rgb = [0x40, 0x80, 0xe0];
+
+/// Something that can be drawn as a grid of coloured cells, like the state of
+/// a puzzle partway through
+pub trait Render {
+    /// Width and height in cells
+    fn size(&self) -> (usize, usize);
+    fn cell(&self, x: usize, y: usize) -> Rgb;
+    /// Lines through the middle of cells, drawn over the top, like the route
+    /// taken through a grid
+    fn paths(&self) -> Vec<(Rgb, Vec<(usize, usize)>)> {
+        Vec::new()
+    }
+    /// A line about what's going on, shown when stepping through
+    fn status(&self) -> String {
+        String::new()
+    }
+}
+
+/// Cells to colour in one by one, for state that isn't already laid out as a
+/// grid
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Rgb>,
+    pub status: String,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![BLACK; width * height],
+            status: String::new(),
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = colour;
+        }
+    }
+}
+
+impl Render for Canvas {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        self.cells[y * self.width + x]
+    }
+
+    fn status(&self) -> String {
+        self.status.clone()
+    }
+}
+
+/// A picture made of pixels
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    /// Draw `frame` with each cell `scale` pixels square
+    pub fn render(frame: &dyn Render, scale: usize) -> Self {
+        let (width, height) = frame.size();
+        let mut image = Self::new(width * scale, height * scale, BLACK);
+        for y in 0..height {
+            for x in 0..width {
+                let colour = frame.cell(x, y);
+                for row in image.pixels[y * scale * image.width..]
+                    .chunks_mut(image.width)
+                    .take(scale)
+                {
+                    row[x * scale..(x + 1) * scale].fill(colour);
+                }
+            }
+        }
+        // a third of a cell wide, through the middle
+        let thickness = scale.div_ceil(3);
+        let offset = (scale - thickness) / 2;
+        for (colour, points) in frame.paths() {
+            for line in points.windows(2) {
+                let ((x0, y0), (x1, y1)) = (line[0], line[1]);
+                let steps = x0.abs_diff(x1).max(y0.abs_diff(y1)) * scale;
+                for i in 0..=steps {
+                    let along = |a: usize, b: usize| {
+                        let (a, b) = ((a * scale) as isize, (b * scale) as isize);
+                        let i = i as isize;
+                        let steps = steps.max(1) as isize;
+                        (a + (b - a) * i / steps) as usize + offset
+                    };
+                    let (px, py) = (along(x0, x1), along(y0, y1));
+                    for dy in 0..thickness {
+                        for dx in 0..thickness {
+                            image.set(px + dx, py + dy, colour);
+                        }
+                    }
+                }
+            }
+        }
+        image
+    }
+
+    /// Lay `frames` out left to right and top to bottom, `columns` to a row,
+    /// with a gap between each
+    pub fn strip(frames: &[Image], columns: usize) -> Self {
+        const GAP: usize = 2;
+        let columns = columns.clamp(1, frames.len().max(1));
+        let rows = frames.len().div_ceil(columns);
+        let cell_w = frames.iter().map(|f| f.width).max().unwrap_or(0) + GAP;
+        let cell_h = frames.iter().map(|f| f.height).max().unwrap_or(0) + GAP;
+        let mut strip = Self::new(columns * cell_w + GAP, rows * cell_h + GAP, WHITE);
+        for (i, frame) in frames.iter().enumerate() {
+            let (left, top) = (GAP + i % columns * cell_w, GAP + i / columns * cell_h);
+            for (y, row) in frame.pixels.chunks(frame.width).enumerate() {
+                let start = (top + y) * strip.width + left;
+                strip.pixels[start..start + frame.width].copy_from_slice(row);
+            }
+        }
+        strip
+    }
+
+    /// Binary PPM, which most image viewers can open
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(self.pixels.as_flattened())
+    }
+
+    /// An uncompressed PNG, so there's nothing to pull in for deflate
+    pub fn write_png(&self, out: &mut impl Write) -> io::Result<()> {
+        fn chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+            out.write_all(&(data.len() as u32).to_be_bytes())?;
+            out.write_all(kind)?;
+            out.write_all(data)?;
+            let crc = crc32(kind.iter().chain(data));
+            out.write_all(&crc.to_be_bytes())
+        }
+
+        // every row starts with a byte saying it isn't filtered
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend(row.as_flattened());
+        }
+        // zlib header, deflate's stored blocks of up to 64KiB, then a checksum
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(0xffff).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend([1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(block) = blocks.next() {
+            zlib.push(u8::from(blocks.peek().is_none()));
+            let len = block.len() as u16;
+            zlib.extend(len.to_le_bytes());
+            zlib.extend((!len).to_le_bytes());
+            zlib.extend(block);
+        }
+        zlib.extend(adler32(&raw).to_be_bytes());
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, then the only compression, filtering and
+        // interlacing methods there are
+        header.extend([8, 2, 0, 0, 0]);
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        chunk(out, b"IHDR", &header)?;
+        chunk(out, b"IDAT", &zlib)?;
+        chunk(out, b"IEND", &[])
+    }
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+/// Draw `frame` as an SVG with each cell `scale` units square
+pub fn svg(frame: &dyn Render, scale: usize) -> String {
+    let (width, height) = frame.size();
+    let hex = |[r, g, b]: Rgb| format!("#{r:02x}{g:02x}{b:02x}");
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         shape-rendering=\"crispEdges\">\n",
+        width * scale,
+        height * scale
+    );
+    for y in 0..height {
+        // one rectangle for each run of the same colour
+        let mut x = 0;
+        while x < width {
+            let colour = frame.cell(x, y);
+            let run = (x..width)
+                .take_while(|&x| frame.cell(x, y) == colour)
+                .count();
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{scale}\" fill=\"{}\"/>",
+                x * scale,
+                y * scale,
+                run * scale,
+                hex(colour)
+            )
+            .unwrap();
+            x += run;
+        }
+    }
+    let centre = |n: usize| n * scale + scale / 2;
+    for (colour, points) in frame.paths() {
+        let points: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{},{}", centre(x), centre(y)))
+            .collect();
+        writeln!(
+            out,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>",
+            points.join(" "),
+            hex(colour),
+            scale.div_ceil(3)
+        )
+        .unwrap();
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Somewhere for frames to go as solvers emit them
+pub trait Sink: Send + Sync {
+    /// `sequence` names the series of frames this one belongs to, like
+    /// `2023-day14-cycle`
+    fn frame(&self, sequence: &str, frame: &dyn Render);
+    /// Whether to also get every step from inside simulation loops, not just
+    /// the occasional frame
+    fn steps(&self) -> bool {
+        false
+    }
+    /// Called once solving is over
+    fn finish(&self) {}
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STEPPING: AtomicBool = AtomicBool::new(false);
+static SINK: RwLock<Option<Box<dyn Sink>>> = RwLock::new(None);
+
+/// Send frames to `sink` from now on
+pub fn set_sink(sink: Box<dyn Sink>) {
+    STEPPING.store(sink.steps(), Ordering::Relaxed);
+    *SINK.write().unwrap() = Some(sink);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether anything wants frames, so solvers can skip building them
+#[inline(always)]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether anything wants every step, see [`step!`](crate::step!)
+#[inline(always)]
+pub fn stepping() -> bool {
+    STEPPING.load(Ordering::Relaxed)
+}
+
+/// Stop building steps once the sink's done with them
+pub fn stop_stepping() {
+    STEPPING.store(false, Ordering::Relaxed);
+}
+
+// `advent_of_code::y2023::day14` and `cycle` make `2023-day14-cycle`
+fn sequence_name(module: &str, name: &str) -> String {
+    let mut parts: Vec<&str> = module
+        .split("::")
+        .skip(1)
+        .map(|part| {
+            part.strip_prefix('y')
+                .filter(|year| year.bytes().all(|b| b.is_ascii_digit()))
+                .unwrap_or(part)
+        })
+        .collect();
+    parts.push(name);
+    parts.join("-")
+}
+
+/// Emit a frame from `module`, for use through [`frame!`](crate::frame!) and
+/// [`step!`](crate::step!)
+pub fn frame(module: &str, name: &str, frame: &dyn Render) {
+    if let Some(sink) = &*SINK.read().unwrap() {
+        sink.frame(&sequence_name(module, name), frame);
+    }
+}
+
+/// Tell the sink solving is over, so it can tidy up
+pub fn finish() {
+    if let Some(sink) = &*SINK.read().unwrap() {
+        sink.finish();
+    }
+}
+
+/// Emit a frame in the sequence `name` if anything wants frames
+///
+/// The frame is only built when it's going to be used.
+#[macro_export]
+macro_rules! frame {
+    ($name:expr, $frame:expr) => {
+        if $crate::visualize::enabled() {
+            $crate::visualize::frame(module_path!(), $name, &$frame);
+        }
+    };
+}
+
+/// Emit a frame for one step of a simulation, which only something stepping
+/// through wants; saving every one of them would be far too many
+#[macro_export]
+macro_rules! step {
+    ($name:expr, $frame:expr) => {
+        if $crate::visualize::stepping() {
+            $crate::visualize::frame(module_path!(), $name, &$frame);
+        }
+    };
+}
+
+/// How [`FrameWriter`] saves frames
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+// Frames kept for each sequence's strip; past this every other one is
+// dropped, so long runs still cover the whole thing
+const STRIP_FRAMES: usize = 64;
+
+#[derive(Default)]
+struct Sequence {
+    written: usize,
+    // every `stride`th frame, for the strip
+    kept: Vec<Image>,
+    stride: usize,
+}
+
+/// Saves each frame as its own file in a directory, plus a strip of every
+/// sequence's frames side by side once solving is over
+pub struct FrameWriter {
+    dir: PathBuf,
+    format: Format,
+    sequences: Mutex<FxHashMap<String, Sequence>>,
+}
+
+impl FrameWriter {
+    pub fn new(dir: &Path, format: Format) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            format,
+            sequences: Mutex::default(),
+        })
+    }
+
+    fn write_image(&self, path: &Path, image: &Image) -> io::Result<()> {
+        let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+        match self.format {
+            Format::Ppm => image.write_ppm(&mut out)?,
+            // strips of SVG frames are drawn as PNGs
+            Format::Png | Format::Svg => image.write_png(&mut out)?,
+        }
+        out.flush()
+    }
+}
+
+// Cells big enough that a frame comes out at a few hundred pixels across
+fn scale_for(frame: &dyn Render) -> usize {
+    let (width, height) = frame.size();
+    (600 / width.max(height).max(1)).clamp(1, 8)
+}
+
+impl Sink for FrameWriter {
+    fn frame(&self, sequence: &str, frame: &dyn Render) {
+        let scale = scale_for(frame);
+        let mut sequences = self.sequences.lock().unwrap();
+        let seq = sequences.entry(sequence.to_string()).or_default();
+        let path = self.dir.join(format!(
+            "{sequence}-{:04}.{}",
+            seq.written,
+            self.format.extension()
+        ));
+        let keep = seq.written.is_multiple_of(seq.stride.max(1));
+        seq.written += 1;
+        let result = match self.format {
+            Format::Svg => std::fs::write(&path, svg(frame, scale)),
+            _ => self.write_image(&path, &Image::render(frame, scale)),
+        };
+        if let Err(e) = result {
+            crate::log!(Level::Warn, "couldn't write {}: {e}", path.display());
+        }
+        if keep {
+            // smaller, so a strip of them isn't enormous
+            seq.kept.push(Image::render(frame, scale.div_ceil(4)));
+            if seq.kept.len() == STRIP_FRAMES {
+                seq.kept = seq.kept.drain(..).step_by(2).collect();
+                seq.stride = seq.stride.max(1) * 2;
+            }
+        }
+    }
+
+    fn finish(&self) {
+        let sequences = std::mem::take(&mut *self.sequences.lock().unwrap());
+        for (name, seq) in sequences {
+            if seq.kept.len() < 2 {
+                continue;
+            }
+            let columns = (seq.kept.len() as f64).sqrt().ceil() as usize;
+            let strip = Image::strip(&seq.kept, columns);
+            let extension = if self.format == Format::Ppm {
+                "ppm"
+            } else {
+                "png"
+            };
+            let path = self.dir.join(format!("{name}-strip.{extension}"));
+            match self.write_image(&path, &strip) {
+                Ok(()) => crate::log!(Level::Info, "wrote {}", path.display()),
+                Err(e) => crate::log!(Level::Warn, "couldn't write {}: {e}", path.display()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Checkerboard;
+
+    impl Render for Checkerboard {
+        fn size(&self) -> (usize, usize) {
+            (3, 2)
+        }
+
+        fn cell(&self, x: usize, y: usize) -> Rgb {
+            if (x + y).is_multiple_of(2) {
+                WHITE
+            } else {
+                BLACK
+            }
+        }
+    }
+
+    #[test]
+    fn render_and_encode() {
+        let image = Image::render(&Checkerboard, 2);
+        assert_eq!((image.width, image.height), (6, 4));
+        assert_eq!(image.pixels[0], WHITE);
+        assert_eq!(image.pixels[2], BLACK);
+        assert_eq!(image.pixels[6 * 2], BLACK);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 6 * 4 * 3);
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"));
+        // the checksums everyone uses to test against
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+
+        assert_eq!(svg(&Checkerboard, 2).matches("<rect").count(), 6);
+        assert_eq!(
+            sequence_name("advent_of_code::y2023::day14", "cycle"),
+            "2023-day14-cycle"
+        );
+    }
+}
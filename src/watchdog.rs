@@ -0,0 +1,84 @@
+use crate::answer::Answer;
+use crate::bench::fmt_ns;
+use crate::log;
+use crate::solution::{ParseError, Runner};
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Why a day didn't give all its answers
+#[derive(Debug)]
+pub enum Failure {
+    Parse(ParseError),
+    Panicked,
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Parse(e) => write!(f, "couldn't parse input: {e}"),
+            Failure::Panicked => write!(f, "panicked"),
+            Failure::TimedOut(after) => {
+                write!(f, "timed out after {}", fmt_ns(after.as_nanos() as f64))
+            }
+        }
+    }
+}
+
+// What the worker thread sends back as it goes
+enum Message {
+    Answer(u8, Answer),
+    Done(Result<(), ParseError>),
+}
+
+/// Solve `input`, calling `f` with the answer to each part as soon as it's
+/// ready, and skipping the other part if `part` is given
+///
+/// With a `timeout` the solver runs on a thread of its own and is given up on
+/// once parsing and both parts have taken that long together. It can't be
+/// stopped, so it carries on in the background until the program exits.
+pub fn solve(
+    runner: &'static dyn Runner,
+    input: &str,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+    f: &mut dyn FnMut(u8, Answer),
+) -> Result<(), Failure> {
+    let Some(timeout) = timeout else {
+        return panic::catch_unwind(AssertUnwindSafe(|| {
+            log::set_day(runner.day());
+            runner.run(input, &mut |n, solve| {
+                if part.is_none_or(|p| p == n) {
+                    f(n, solve());
+                }
+            })
+        }))
+        .map_err(|_| Failure::Panicked)?
+        .map_err(Failure::Parse);
+    };
+
+    let deadline = Instant::now() + timeout;
+    let (tx, rx) = mpsc::channel();
+    let input = input.to_string();
+    std::thread::spawn(move || {
+        log::set_day(runner.day());
+        let result = runner.run(&input, &mut |n, solve| {
+            if part.is_none_or(|p| p == n) {
+                // nobody's listening any more if it timed out
+                let _ = tx.send(Message::Answer(n, solve()));
+            }
+        });
+        let _ = tx.send(Message::Done(result));
+    });
+    loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Message::Answer(n, answer)) => f(n, answer),
+            Ok(Message::Done(result)) => return result.map_err(Failure::Parse),
+            Err(RecvTimeoutError::Timeout) => return Err(Failure::TimedOut(timeout)),
+            // the thread only hangs up early if it panicked
+            Err(RecvTimeoutError::Disconnected) => return Err(Failure::Panicked),
+        }
+    }
+}
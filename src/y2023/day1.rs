@@ -1,7 +1,39 @@
-const INPUT: &str = include_str!("inputs/1.txt");
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 
-pub fn part1() -> usize {
-    INPUT
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        for line in input.lines() {
+            if line.is_empty() {
+                return Err(ParseError::at(input, line, "a line of letters and digits"));
+            }
+            if let Some(i) = line.find(|c: char| !c.is_ascii_alphanumeric()) {
+                return Err(ParseError::at(input, &line[i..], "a letter or digit"));
+            }
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    input
         .lines()
         .map(|l| {
             (
@@ -22,8 +54,8 @@ pub fn part1() -> usize {
         .sum()
 }
 
-pub fn part2() -> usize {
-    INPUT
+pub fn part2(input: &str) -> usize {
+    input
         .lines()
         .map(|l| {
             let matcher = |window: &[u8]| match *window {
@@ -99,3 +131,61 @@ impl<'a, T> DoubleEndedIterator for WindowsUpto<'a, T> {
         }
     }
 }
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Lines of letters with digits and spelled out digits mixed in
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let lines: Vec<String> = (0..1000 * scale)
+        .map(|_| {
+            let len = rng.gen_range(1..=50);
+            // part 1 needs at least one real digit
+            let digit_at = rng.gen_range(0..len);
+            let mut line = String::new();
+            for i in 0..len {
+                if i == digit_at || rng.gen_bool(0.05) {
+                    line.push(rng.gen_range('1'..='9'));
+                } else if rng.gen_bool(0.1) {
+                    line.push_str(DIGIT_WORDS.choose(rng).unwrap());
+                } else {
+                    line.push(rng.gen_range('a'..='z'));
+                }
+            }
+            line
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const EXAMPLE2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day1>(EXAMPLE, 1), "142");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day1>(EXAMPLE2, 2), "281");
+    }
+}
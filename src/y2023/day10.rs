@@ -1,7 +1,46 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use rustc_hash::FxHashSet;
+use std::cmp::Ordering;
 use std::{cell::OnceCell, num::NonZeroUsize};
 
-const INPUT: &str = include_str!("inputs/10.txt");
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        check_grid(input, input, "a pipe, '.' or 'S'", |b| {
+            b"|-LJ7F.S".contains(&b)
+        })?;
+        let mut starts = input.match_indices('S').map(|(i, _)| i);
+        if starts.next().is_none() {
+            return Err(ParseError::at_offset(
+                input,
+                input.len(),
+                "an 'S' to start from",
+            ));
+        }
+        if let Some(i) = starts.next() {
+            return Err(ParseError::at_offset(input, i, "only one 'S'"));
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
 
 struct Grid<'a> {
     src: &'a [u8],
@@ -12,11 +51,8 @@ struct Grid<'a> {
 impl<'a> Grid<'a> {
     pub fn new(src: &'a str) -> Self {
         let src = src.as_bytes();
-        let width = src
-            .iter()
-            .position(|&b| b == b'\n')
-            .and_then(|p| NonZeroUsize::new(p + 1))
-            .unwrap();
+        let width = src.iter().position(|&b| b == b'\n').unwrap_or(src.len());
+        let width = NonZeroUsize::new(width + 1).unwrap();
         Self {
             src,
             width,
@@ -49,7 +85,7 @@ impl<'a> Grid<'a> {
     }
 
     pub fn height(&self) -> usize {
-        self.src.len() / self.width
+        self.src.len().div_ceil(self.width.get())
     }
 }
 
@@ -157,15 +193,18 @@ impl<'a> Iterator for Pipes<'a> {
     }
 }
 
-pub fn part1() -> usize {
-    let grid = Grid::new(INPUT);
-    (grid.pipes().count() + 1).div_ceil(2)
+pub fn part1(input: &str) -> usize {
+    let grid = Grid::new(input);
+    // the furthest point is halfway round the loop
+    grid.pipes().count() / 2
 }
 
-pub fn part2() -> usize {
-    let grid = Grid::new(INPUT);
+pub fn part2(input: &str) -> usize {
+    let grid = Grid::new(input);
     let mut points: FxHashSet<_> = grid.pipes().collect();
     let mut count = 0;
+    // only kept for drawing
+    let mut inside = FxHashSet::default();
 
     for y in (0..grid.height()) {
         #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -199,9 +238,155 @@ pub fn part2() -> usize {
                 };
             } else if state == State::Inside {
                 count += 1;
+                if visualize::enabled() {
+                    inside.insert((x, y));
+                }
             }
         }
     }
 
+    crate::frame!(
+        "enclosed",
+        Enclosed {
+            grid: &grid,
+            points: &points,
+            inside: &inside
+        }
+    );
     count
 }
+
+// The loop, with the tiles it encloses filled in
+struct Enclosed<'a> {
+    grid: &'a Grid<'a>,
+    points: &'a FxHashSet<(usize, usize)>,
+    inside: &'a FxHashSet<(usize, usize)>,
+}
+
+impl Render for Enclosed<'_> {
+    fn size(&self) -> (usize, usize) {
+        (self.grid.width.get() - 1, self.grid.height())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        if self.points.contains(&(x, y)) {
+            visualize::GREEN
+        } else if self.inside.contains(&(x, y)) {
+            visualize::BLUE
+        } else {
+            visualize::BLACK
+        }
+    }
+}
+
+// One loop around a random blob, with junk pipes everywhere else
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(140, scale);
+    let mut rows = generate::grid(rng, size, size, |rng, _, _| {
+        *b"|-LJ7F....".choose(rng).unwrap()
+    });
+    // the loop runs round the corners of the blob's cells, one tile in from
+    // the edge
+    let outline: Vec<_> = generate::outline(rng, size - 3, size - 3)
+        .into_iter()
+        .map(|(x, y)| (x + 1, y + 1))
+        .collect();
+    for (i, &(x, y)) in outline.iter().enumerate() {
+        let prev = outline[(i + outline.len() - 1) % outline.len()];
+        let next = outline[(i + 1) % outline.len()];
+        let side = |(px, py): (usize, usize)| match (px.cmp(&x), py.cmp(&y)) {
+            (_, Ordering::Less) => 1,
+            (Ordering::Greater, _) => 2,
+            (_, Ordering::Greater) => 4,
+            _ => 8,
+        };
+        rows[y][x] = match side(prev) | side(next) {
+            0b0101 => b'|',
+            0b1010 => b'-',
+            0b0011 => b'L',
+            0b1001 => b'J',
+            0b1100 => b'7',
+            0b0110 => b'F',
+            _ => unreachable!(),
+        };
+    }
+    // the start's pipe is worked out from its neighbours, so only the loop
+    // can connect to it
+    let loop_tiles: FxHashSet<_> = outline.iter().copied().collect();
+    let (x, y) = *outline.choose(rng).unwrap();
+    rows[y][x] = b'S';
+    for (nx, ny) in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+        if !loop_tiles.contains(&(nx, ny)) {
+            rows[ny][nx] = b'.';
+        }
+    }
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+    const EXAMPLE2: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+
+    const EXAMPLE3: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const EXAMPLE4: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const EXAMPLE5: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day10>(EXAMPLE, 1), "4");
+        assert_eq!(solve::<Day10>(EXAMPLE2, 1), "8");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day10>(EXAMPLE3, 2), "4");
+        assert_eq!(solve::<Day10>(EXAMPLE4, 2), "8");
+        assert_eq!(solve::<Day10>(EXAMPLE5, 2), "10");
+    }
+}
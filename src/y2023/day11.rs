@@ -0,0 +1,145 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::num::NonZeroUsize;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+    type Parsed<'a> = Space;
+
+    fn parse(input: &str) -> Result<Space, ParseError> {
+        parse(input)
+    }
+
+    fn part1(space: &Space) -> Answer {
+        part1(space).into()
+    }
+
+    fn part2(space: &Space) -> Answer {
+        part2(space).into()
+    }
+}
+
+pub struct Space {
+    galaxies: Vec<(usize, usize)>,
+    populated_rows: Vec<bool>,
+    populated_cols: Vec<bool>,
+}
+
+impl Space {
+    pub fn path_count<const GROWTH: usize>(&self) -> usize {
+        let mut sum = 0;
+        for (i, &from) in self.galaxies.iter().enumerate() {
+            for (j, &to) in self.galaxies.iter().enumerate().skip(i) {
+                if i == j {
+                    continue;
+                }
+                let (start_x, end_x) = if from.0 < to.0 {
+                    (from.0, to.0)
+                } else {
+                    (to.0, from.0)
+                };
+                let x_expansion = (start_x..end_x)
+                    .filter(|&x| !self.populated_cols[x])
+                    .count();
+                sum += end_x - start_x + x_expansion * (GROWTH - 1);
+
+                let (start_y, end_y) = if from.1 < to.1 {
+                    (from.1, to.1)
+                } else {
+                    (to.1, from.1)
+                };
+                let y_expansion = (start_y..end_y)
+                    .filter(|&y| !self.populated_rows[y])
+                    .count();
+                sum += end_y - start_y + y_expansion * (GROWTH - 1);
+            }
+        }
+        sum
+    }
+}
+
+pub fn parse(src: &str) -> Result<Space, ParseError> {
+    let width = check_grid(src, src, "'.' or '#'", |b| b == b'.' || b == b'#')?;
+    let width = NonZeroUsize::new(width + 1).unwrap();
+    let galaxies: Vec<_> = src
+        .bytes()
+        .enumerate()
+        .filter(|&(_, b)| b == b'#')
+        .map(|(i, _)| (i % width, i / width))
+        .collect();
+    let Some(&(_, height)) = galaxies.last() else {
+        return Err(ParseError::at_offset(src, src.len(), "a galaxy"));
+    };
+    let mut populated_rows = vec![false; height + 1];
+    let mut populated_cols = vec![false; width.get()];
+    for &(x, y) in galaxies.iter() {
+        populated_rows[y] = true;
+        populated_cols[x] = true;
+    }
+    Ok(Space {
+        galaxies,
+        populated_cols,
+        populated_rows,
+    })
+}
+
+pub fn part1(space: &Space) -> usize {
+    space.path_count::<2>()
+}
+
+pub fn part2(space: &Space) -> usize {
+    space.path_count::<1000000>()
+}
+
+// Sparse galaxies, with some rows and columns left empty to expand
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(140, scale);
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.06)).collect();
+    let empty_cols: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.06)).collect();
+    let mut rows = generate::grid(rng, size, size, |rng, x, y| {
+        if !empty_rows[y] && !empty_cols[x] && rng.gen_bool(0.025) {
+            b'#'
+        } else {
+            b'.'
+        }
+    });
+    // there has to be at least one galaxy
+    rows[0][0] = b'#';
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day11>(EXAMPLE, 1), "374");
+    }
+
+    #[test]
+    fn part2_example() {
+        let space = parse(EXAMPLE).unwrap();
+        assert_eq!(space.path_count::<10>(), 1030);
+        assert_eq!(space.path_count::<100>(), 8410);
+    }
+}
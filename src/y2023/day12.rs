@@ -1,12 +1,34 @@
+use crate::answer::Answer;
+use crate::solution::{parse_number, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
 use rustc_hash::FxHashMap;
 use std::fmt::{Formatter, Write};
 use std::hash::{Hash, Hasher};
 
-const INPUT: &str = include_str!("inputs/12.txt");
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hot Springs";
+    type Parsed<'a> = Vec<Row>;
+
+    fn parse(input: &str) -> Result<Vec<Row>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(rows: &Vec<Row>) -> Answer {
+        part1(rows).into()
+    }
+
+    fn part2(rows: &Vec<Row>) -> Answer {
+        part2(rows).into()
+    }
+}
 
 #[repr(u8)]
 #[derive(Copy, Clone, Hash, PartialEq, Eq)]
-enum SpringKind {
+pub enum SpringKind {
     Operational = b'.',
     Damaged = b'#',
     Unknown = b'?',
@@ -98,36 +120,50 @@ fn find_places<'data>(
     CacheResult::Miss(sum)
 }
 
-fn parse_input_line(line: &str) -> (Vec<SpringKind>, Vec<u8>) {
-    let (springs, groups) = line.split_once(' ').unwrap();
+fn parse_input_line(input: &str, line: &str) -> Result<Row, ParseError> {
+    let Some((springs, groups)) = line.split_once(' ') else {
+        return Err(ParseError::at(input, &line[line.len()..], "' '"));
+    };
     let springs = springs
         .bytes()
-        .map(|b| match b {
-            b'.' => SpringKind::Operational,
-            b'#' => SpringKind::Damaged,
-            b'?' => SpringKind::Unknown,
-            _ => unreachable!(),
+        .enumerate()
+        .map(|(i, b)| match b {
+            b'.' => Ok(SpringKind::Operational),
+            b'#' => Ok(SpringKind::Damaged),
+            b'?' => Ok(SpringKind::Unknown),
+            _ => Err(ParseError::at(input, &line[i..], "'.', '#' or '?'")),
         })
-        .collect::<Vec<_>>();
-    let groups: Vec<u8> = groups.split(',').map(|n| n.parse().unwrap()).collect();
-    (springs, groups)
+        .collect::<Result<Vec<_>, _>>()?;
+    let groups = groups
+        .split(',')
+        .map(|n| parse_number(input, n))
+        .collect::<Result<Vec<u8>, _>>()?;
+    Ok((springs, groups))
+}
+
+// Each row of springs along with its damaged group sizes
+pub type Row = (Vec<SpringKind>, Vec<u8>);
+
+pub fn parse(input: &str) -> Result<Vec<Row>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse_input_line(input, line))
+        .collect()
 }
 
-pub fn part1() -> usize {
+pub fn part1(rows: &[Row]) -> usize {
     let mut sum = 0;
-    for line in INPUT.lines() {
-        let (springs, groups) = parse_input_line(line);
+    for (springs, groups) in rows {
         let mut cache = FxHashMap::default();
-        let amnt = find_places(&mut cache, &groups, &springs);
+        let amnt = find_places(&mut cache, groups, springs);
         sum += amnt.value();
     }
     sum
 }
 
-pub fn part2() -> usize {
+pub fn part2(rows: &[Row]) -> usize {
     let mut sum = 0;
-    for line in INPUT.lines() {
-        let (springs, groups) = parse_input_line(line);
+    for (springs, groups) in rows {
         let springs = {
             let mut new = Vec::with_capacity(springs.len() * 5 + 5);
             for _ in 0..5 {
@@ -145,3 +181,58 @@ pub fn part2() -> usize {
     }
     sum
 }
+
+// Rows written out from an arrangement of damaged springs, with some of
+// them hidden as '?' so there's always at least one way to fit the groups
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let rows: Vec<String> = (0..1000 * scale)
+        .map(|_| {
+            let groups: Vec<usize> = (0..rng.gen_range(1..=5))
+                .map(|_| rng.gen_range(1..=4))
+                .collect();
+            let mut springs = Vec::new();
+            for (i, &group) in groups.iter().enumerate() {
+                let gap = rng.gen_range(usize::from(i > 0)..=3);
+                springs.extend(std::iter::repeat_n(b'.', gap));
+                springs.extend(std::iter::repeat_n(b'#', group));
+            }
+            springs.extend(std::iter::repeat_n(b'.', rng.gen_range(0..=3)));
+            for spring in springs.iter_mut() {
+                if rng.gen_bool(0.4) {
+                    *spring = b'?';
+                }
+            }
+            let groups: Vec<String> = groups.iter().map(usize::to_string).collect();
+            format!(
+                "{} {}",
+                String::from_utf8(springs).unwrap(),
+                groups.join(",")
+            )
+        })
+        .collect();
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day12>(EXAMPLE, 1), "21");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day12>(EXAMPLE, 2), "525152");
+    }
+}
@@ -0,0 +1,217 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Point of Incidence";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        for pattern in input.split("\n\n") {
+            if pattern.is_empty() {
+                return Err(ParseError::at(input, pattern, "a pattern"));
+            }
+            check_grid(input, pattern, "'.' or '#'", |b| b == b'.' || b == b'#')?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+struct Pattern<'a> {
+    source: &'a [u8],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Pattern<'a> {
+    pub fn new(source: &'a [u8]) -> Self {
+        let width = source
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(source.len())
+            + 1;
+        let height = source.len().div_ceil(width);
+        Self {
+            source,
+            width,
+            height,
+        }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [u8]> + ExactSizeIterator {
+        self.source.chunks(self.width).map(|slice| match slice {
+            [start @ .., b'\n'] | start => start,
+        })
+    }
+}
+
+fn find_reflection_value(pat: impl AsRef<[u8]>, ignore: Option<usize>) -> Option<usize> {
+    fn is_mirrored<T: PartialEq>(row: &[T], mirror: usize) -> bool {
+        let (mut before, mut after) = row.split_at(mirror);
+        while let Some(((before_last, before_rest), (after_first, after_rest))) =
+            before.split_last().zip(after.split_first())
+        {
+            if before_last != after_first {
+                return false;
+            }
+            before = before_rest;
+            after = after_rest;
+        }
+        true
+    }
+    let pat = Pattern::new(pat.as_ref());
+    // Try each column
+    let rows: Vec<_> = pat.rows().collect();
+    for reflect_col in 1..pat.width - 1 {
+        if rows.iter().all(|row| is_mirrored(row, reflect_col)) && ignore != Some(reflect_col) {
+            return Some(reflect_col);
+        }
+    }
+
+    for reflect_row in 1..pat.height {
+        if is_mirrored(&rows, reflect_row) && ignore != Some(100 * reflect_row) {
+            return Some(100 * reflect_row);
+        }
+    }
+    None
+}
+
+pub fn part1(input: &str) -> usize {
+    input
+        .split("\n\n")
+        .map(|p| find_reflection_value(p, None).unwrap())
+        .sum()
+}
+
+// The reflection found once the smudge is cleaned up
+fn smudged_value(pat: &str) -> Option<usize> {
+    let mut pattern = pat.to_owned().into_bytes();
+    let orig = find_reflection_value(&pattern, None)?;
+    fn flip(b: &mut u8) {
+        match *b {
+            b'#' => *b = b'.',
+            b'.' => *b = b'#',
+            _ => (),
+        }
+    }
+    for i in 0..pattern.len() {
+        if i != 0 {
+            flip(&mut pattern[i - 1]);
+        }
+        if pattern[i] == b'\n' {
+            continue;
+        }
+        flip(&mut pattern[i]);
+        if let Some(val) = find_reflection_value(&pattern, Some(orig)) {
+            debug_assert!(val != orig);
+            return Some(val);
+        }
+    }
+    None
+}
+
+pub fn part2(input: &str) -> usize {
+    input.split("\n\n").map(|p| smudged_value(p).unwrap()).sum()
+}
+
+// A pattern mirrored across a random line, with its first two rows the
+// same. Flipping a cell outside those rows then leaves the pair as the only
+// reflection, and the original line as the one behind the smudge.
+fn smudged_pattern(rng: &mut StdRng) -> Option<String> {
+    let (width, height) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    let mirror = rng.gen_range(1..width);
+    let mut rows = generate::grid(rng, width, height, |rng, _, _| *b"#.".choose(rng).unwrap());
+    for row in rows.iter_mut() {
+        for x in mirror..width.min(2 * mirror) {
+            row[x] = row[2 * mirror - 1 - x];
+        }
+    }
+    rows[1] = rows[0].clone();
+    let x = rng.gen_range(mirror.saturating_sub(width - mirror)..mirror);
+    let y = rng.gen_range(2..height);
+    rows[y][x] = if rows[y][x] == b'#' { b'.' } else { b'#' };
+
+    // turn it around so the reflections aren't always in the same places
+    if rng.gen() {
+        rows.reverse();
+    }
+    if rng.gen() {
+        rows = (0..width)
+            .map(|x| rows.iter().map(|row| row[x]).collect())
+            .collect();
+    }
+    // make sure nothing else lines up by chance
+    let pattern = generate::join_rows(&rows);
+    let first = find_reflection_value(&pattern, None)?;
+    if find_reflection_value(&pattern, Some(first)).is_some() {
+        return None;
+    }
+    smudged_value(&pattern)?;
+    Some(pattern)
+}
+
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let patterns: Vec<String> = (0..100 * scale)
+        .map(|_| {
+            std::iter::repeat_with(|| smudged_pattern(rng))
+                .find_map(|p| p)
+                .unwrap()
+        })
+        .collect();
+    patterns.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day13>(EXAMPLE, 1), "405");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day13>(EXAMPLE, 2), "400");
+    }
+
+    #[test]
+    fn ragged_pattern() {
+        assert_eq!(
+            parse_error::<Day13>("#.#\n..#\n\n#.\n.#."),
+            "day 13, line 5, column 3: expected end of line, found '.'"
+        );
+    }
+}
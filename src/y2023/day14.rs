@@ -1,9 +1,33 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::Rng;
 use rustc_hash::FxHashMap;
 use std::collections::hash_map::Entry;
 use std::fmt::{Debug, Formatter, Write};
 use std::hash::{Hash, Hasher};
 
-const INPUT: &str = include_str!("inputs/14.txt");
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Parabolic Reflector Dish";
+    type Parsed<'a> = Grid;
+
+    fn parse(input: &str) -> Result<Grid, ParseError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Grid) -> Answer {
+        part1(grid).into()
+    }
+
+    fn part2(grid: &Grid) -> Answer {
+        part2(grid).into()
+    }
+}
 
 #[repr(u8)]
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -20,14 +44,14 @@ impl Debug for CellKind {
 }
 
 #[derive(Clone, Eq, PartialEq)]
-struct Grid {
+pub struct Grid {
     content: Vec<CellKind>,
     width: usize,
 }
 
 impl Grid {
-    pub fn new(src: &str) -> Self {
-        let width = src.bytes().position(|b| b == b'\n').unwrap();
+    pub fn new(src: &str) -> Result<Self, ParseError> {
+        let width = check_grid(src, src, "'O', '#' or '.'", |b| b"O#.".contains(&b))?;
         let content = src
             .bytes()
             .filter_map(|b| {
@@ -39,7 +63,7 @@ impl Grid {
                 })
             })
             .collect();
-        Self { content, width }
+        Ok(Self { content, width })
     }
 
     pub fn slide_up(&mut self) {
@@ -133,9 +157,56 @@ impl Grid {
     }
 }
 
-pub fn part1() -> usize {
-    let mut grid = Grid::new(INPUT);
+impl Render for Grid {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.content.len() / self.width)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        match self.content[y * self.width + x] {
+            CellKind::Round => visualize::ORANGE,
+            CellKind::Cube => visualize::GREY,
+            CellKind::Empty => visualize::BLACK,
+        }
+    }
+
+    fn status(&self) -> String {
+        format!("load {}", self.weight())
+    }
+}
+
+// The platform partway through a spin cycle: which cycle, and which way it
+// was just tilted
+struct Tilted<'a>(&'a Grid, usize, &'static str);
+
+impl Render for Tilted<'_> {
+    fn size(&self) -> (usize, usize) {
+        self.0.size()
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        self.0.cell(x, y)
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "cycle {}, tilted {}, load {}",
+            self.1,
+            self.2,
+            self.0.weight()
+        )
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid, ParseError> {
+    Grid::new(input)
+}
+
+pub fn part1(grid: &Grid) -> usize {
+    let mut grid = grid.clone();
+    crate::frame!("tilt", grid);
     grid.slide_up();
+    crate::frame!("tilt", grid);
     grid.weight()
 }
 
@@ -145,8 +216,8 @@ impl Hash for Grid {
     }
 }
 
-pub fn part2() -> usize {
-    let mut grid = Grid::new(INPUT);
+pub fn part2(grid: &Grid) -> usize {
+    let mut grid = grid.clone();
     let mut cache = FxHashMap::default();
 
     let mut i = 0;
@@ -163,10 +234,61 @@ pub fn part2() -> usize {
             }
         }
         grid.slide_up();
+        crate::step!("spin", Tilted(&grid, i, "north"));
         grid.slide_left();
+        crate::step!("spin", Tilted(&grid, i, "west"));
         grid.slide_down();
+        crate::step!("spin", Tilted(&grid, i, "south"));
         grid.slide_right();
+        crate::frame!("spin", Tilted(&grid, i, "east"));
         i += 1;
     }
     grid.weight()
 }
+
+// Round rocks and cube rocks scattered over the platform
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(100, scale);
+    let rows = generate::grid(rng, size, size, |rng, _, _| match rng.gen_range(0..100) {
+        0..=19 => b'O',
+        20..=34 => b'#',
+        _ => b'.',
+    });
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day14>(EXAMPLE, 1), "136");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day14>(EXAMPLE, 2), "64");
+    }
+
+    #[test]
+    fn short_row() {
+        assert_eq!(
+            parse_error::<Day14>("O.#\n.O.\n#"),
+            "day 14, line 3, column 2: expected a row 3 wide, found end of input"
+        );
+    }
+}
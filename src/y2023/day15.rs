@@ -0,0 +1,126 @@
+use crate::answer::Answer;
+use crate::solution::{parse_number, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Lens Library";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        for step in input.split(',') {
+            check_step(input, step)?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+// Make sure a step is a label then `=` and a focal length, or `-`
+fn check_step(input: &str, step: &str) -> Result<(), ParseError> {
+    let label = step
+        .find(|c: char| !c.is_ascii_lowercase())
+        .unwrap_or(step.len());
+    if label == 0 {
+        return Err(ParseError::at(input, step, "a label of lowercase letters"));
+    }
+    match &step[label..] {
+        "-" => Ok(()),
+        rest => match rest.strip_prefix('=') {
+            Some(length) if !length.is_empty() && length.bytes().all(|b| b.is_ascii_digit()) => {
+                parse_number::<u8>(input, length).map(drop)
+            }
+            Some(length) => Err(ParseError::at(input, length, "a focal length")),
+            None => Err(ParseError::at(input, rest, "'=' or '-'")),
+        },
+    }
+}
+
+fn hash_string(s: &str) -> usize {
+    s.bytes().fold(0, |acc, b| ((acc + b as usize) * 17) % 256)
+}
+
+pub fn part1(input: &str) -> usize {
+    input.split(',').map(hash_string).sum()
+}
+
+pub fn part2(input: &str) -> usize {
+    let mut map: [_; 256] = std::array::from_fn(|_| Vec::<(&str, u8)>::new());
+    for operation in input.split(',') {
+        if let Some((lbl, num)) = operation.split_once('=') {
+            let idx = hash_string(lbl);
+            let num = num.parse::<u8>().unwrap();
+            if let Some(lens) = map[idx].iter_mut().find(|(lbl2, _)| *lbl2 == lbl) {
+                lens.1 = num;
+            } else {
+                map[idx].push((lbl, num));
+            }
+        } else if let Some(lbl) = operation.strip_suffix('-') {
+            let idx = hash_string(lbl);
+            if let Some(pos) = map[idx].iter().position(|(lbl2, _)| *lbl2 == lbl) {
+                map[idx].remove(pos);
+            }
+        } else {
+            unreachable!("checked by `check_step`")
+        };
+    }
+    let mut sum = 0;
+    for (i, boxx) in map.iter().enumerate() {
+        for (j, lens) in boxx.iter().enumerate() {
+            sum += (i + 1) * (j + 1) * lens.1 as usize;
+        }
+    }
+    sum
+}
+
+// Steps picking from a pool of labels, so lenses get replaced and removed
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let labels: Vec<String> = (0..500 * scale)
+        .map(|_| {
+            (0..rng.gen_range(2..=6))
+                .map(|_| rng.gen_range('a'..='z'))
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..4000 * scale)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_bool(0.4) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.gen_range(1..=9))
+            }
+        })
+        .collect();
+    steps.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day15>(EXAMPLE, 1), "1320");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day15>(EXAMPLE, 2), "145");
+    }
+}
@@ -0,0 +1,276 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "The Floor Will Be Lava";
+    type Parsed<'a> = Grid<'a>;
+
+    fn parse(input: &str) -> Result<Grid<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Grid) -> Answer {
+        part1(grid).into()
+    }
+
+    fn part2(grid: &Grid) -> Answer {
+        part2(grid).into()
+    }
+}
+
+pub struct Grid<'a> {
+    src: &'a [u8],
+    // width including newline
+    width: usize,
+    height: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Hash, Eq)]
+#[repr(u8)]
+enum Direction {
+    North = 0,
+    East,
+    South,
+    West,
+}
+
+impl<'a> Grid<'a> {
+    pub fn new(src: &'a str) -> Result<Self, ParseError> {
+        let width = check_grid(src, src, "'.', a mirror or a splitter", |b| {
+            b".|-/\\".contains(&b)
+        })?;
+        Ok(Self {
+            src: src.as_bytes(),
+            width,
+            height: src.len().div_ceil(width + 1),
+        })
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&u8> {
+        self.src.get(y * (self.width + 1) + x)
+    }
+}
+
+type Pos = (usize, usize);
+
+// Every tile the beam passes through, with the directions it went through in
+fn energize(grid: &Grid, mut pos: Pos, mut dir: Direction) -> FxHashMap<Pos, [bool; 4]> {
+    let mut points_todo: Vec<(Pos, Direction)> = Vec::new();
+    // The array corresponds to the 4 directions (N, E, S, W).
+    // If a Pos has been visited going North before, [0] will be `true`
+    let mut visited: FxHashMap<Pos, [bool; 4]> =
+        FxHashMap::with_capacity_and_hasher(grid.src.len(), Default::default());
+    fn next_pos((x, y): Pos, dir: Direction, grid: &Grid) -> Option<Pos> {
+        Some(match dir {
+            Direction::North if y > 0 => (x, y - 1),
+            Direction::East if x + 1 < grid.width => (x + 1, y),
+            Direction::South if y + 1 < grid.height => (x, y + 1),
+            Direction::West if x > 0 => (x - 1, y),
+            _ => return None,
+        })
+    }
+
+    loop {
+        match visited.entry(pos) {
+            Entry::Occupied(o) if o.get()[dir as usize] => {
+                if let Some((npos, ndir)) = points_todo.pop() {
+                    pos = npos;
+                    dir = ndir
+                } else {
+                    break;
+                }
+            }
+            Entry::Occupied(mut o) => {
+                o.get_mut()[dir as usize] = true;
+            }
+            Entry::Vacant(mut v) => {
+                let mut directions = [false; 4];
+                directions[dir as usize] = true;
+                v.insert(directions);
+            }
+        }
+        crate::step!(
+            "beam",
+            Energized {
+                grid,
+                visited: &visited,
+                beam: Some((pos, dir)),
+                waiting: points_todo.len(),
+            }
+        );
+        let cell = grid.get(pos).unwrap();
+        dir = match (cell, dir) {
+            (b'.', _) => dir,
+            (b'/', Direction::North) => Direction::East,
+            (b'/', Direction::East) => Direction::North,
+            (b'/', Direction::South) => Direction::West,
+            (b'/', Direction::West) => Direction::South,
+            (b'\\', Direction::North) => Direction::West,
+            (b'\\', Direction::East) => Direction::South,
+            (b'\\', Direction::South) => Direction::East,
+            (b'\\', Direction::West) => Direction::North,
+            (b'|', Direction::North | Direction::South) => dir,
+            (b'-', Direction::East | Direction::West) => dir,
+            (b'|', _) => {
+                points_todo.push((pos, Direction::South));
+                Direction::North
+            }
+            (b'-', _) => {
+                points_todo.push((pos, Direction::East));
+                Direction::West
+            }
+            _ => unreachable!("{pos:?}, '{}'", cell.escape_ascii()),
+        };
+        if let Some(npos) = next_pos(pos, dir, grid) {
+            pos = npos;
+        } else if let Some((npos, ndir)) = points_todo.pop() {
+            pos = npos;
+            dir = ndir
+        } else {
+            break;
+        }
+    }
+    visited
+}
+
+fn solve(grid: &Grid, pos: Pos, dir: Direction) -> usize {
+    energize(grid, pos, dir).len()
+}
+
+// The contraption with the energized tiles lit up, and where the beam is if
+// it's still going
+struct Energized<'a, 'g> {
+    grid: &'a Grid<'g>,
+    visited: &'a FxHashMap<Pos, [bool; 4]>,
+    beam: Option<(Pos, Direction)>,
+    // beams split off and still to follow
+    waiting: usize,
+}
+
+impl Render for Energized<'_, '_> {
+    fn size(&self) -> (usize, usize) {
+        (self.grid.width, self.grid.height)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        match self.grid.get((x, y)) {
+            _ if self.beam.is_some_and(|(pos, _)| pos == (x, y)) => visualize::WHITE,
+            Some(b'.') | None if self.visited.contains_key(&(x, y)) => visualize::YELLOW,
+            Some(b'.') | None => visualize::BLACK,
+            _ if self.visited.contains_key(&(x, y)) => visualize::ORANGE,
+            _ => visualize::GREY,
+        }
+    }
+
+    fn status(&self) -> String {
+        let energized = self.visited.len();
+        match self.beam {
+            Some((pos, dir)) => format!(
+                "beam at {pos:?} heading {dir:?}, {energized} energized, {} waiting",
+                self.waiting
+            ),
+            None => format!("{energized} energized"),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid<'_>, ParseError> {
+    Grid::new(input)
+}
+
+pub fn part1(grid: &Grid) -> usize {
+    let visited = energize(grid, (0, 0), Direction::East);
+    crate::frame!(
+        "energized",
+        Energized {
+            grid,
+            visited: &visited,
+            beam: None,
+            waiting: 0,
+        }
+    );
+    visited.len()
+}
+
+pub fn part2(g: &Grid) -> usize {
+    let mut largest = 0;
+    for x in 0..g.width {
+        largest = largest.max(solve(g, (x, 0), Direction::South)).max(solve(
+            g,
+            (x, g.height - 1),
+            Direction::North,
+        ));
+    }
+    for y in 0..g.height {
+        largest = largest.max(solve(g, (0, y), Direction::East)).max(solve(
+            g,
+            (g.width - 1, y),
+            Direction::West,
+        ));
+    }
+    largest
+}
+
+// Mostly empty space with mirrors and splitters dotted around
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(110, scale);
+    let rows = generate::grid(rng, size, size, |rng, _, _| {
+        if rng.gen_bool(0.1) {
+            *b"/\\|-".choose(rng).unwrap()
+        } else {
+            b'.'
+        }
+    });
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day16>(EXAMPLE, 1), "46");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day16>(EXAMPLE, 2), "51");
+    }
+
+    #[test]
+    fn part2_from_bottom_row() {
+        // only a beam along the last row gets split both ways
+        assert_eq!(solve::<Day16>("...\n...\n.|.", 2), "4");
+    }
+
+    #[test]
+    fn unknown_tile() {
+        assert_eq!(
+            parse_error::<Day16>(".|.\n.x."),
+            "day 16, line 2, column 2: expected '.', a mirror or a splitter, found 'x'"
+        );
+    }
+}
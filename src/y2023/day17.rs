@@ -1,9 +1,33 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::Rng;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 
-const INPUT: &str = include_str!("inputs/17.txt");
+pub struct Day17;
 
-struct Grid<'a> {
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Clumsy Crucible";
+    type Parsed<'a> = Grid<'a>;
+
+    fn parse(input: &str) -> Result<Grid<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Grid) -> Answer {
+        part1(grid).into()
+    }
+
+    fn part2(grid: &Grid) -> Answer {
+        part2(grid).into()
+    }
+}
+
+pub struct Grid<'a> {
     src: &'a [u8],
     // width including newline
     width: usize,
@@ -173,13 +197,15 @@ impl<'a, PL> Debug for Pos<'a, PL> {
 }
 
 impl<'a> Grid<'a> {
-    pub fn new(src: &'a str) -> Self {
-        let width = src.bytes().position(|b| b == b'\n').unwrap();
-        Self {
+    pub fn new(src: &'a str) -> Result<Self, ParseError> {
+        let width = check_grid(src, src, "a digit from '1' to '9'", |b| {
+            matches!(b, b'1'..=b'9')
+        })?;
+        Ok(Self {
             src: src.as_bytes(),
             width,
             height: src.len().div_ceil(width + 1),
-        }
+        })
     }
 
     pub fn get(&self, x: usize, y: usize) -> Option<usize> {
@@ -191,26 +217,114 @@ impl<'a> Grid<'a> {
         }
     }
 }
-pub fn part1() -> usize {
-    let grid = Grid::new(INPUT);
-    let start = Pos(0, 0, &grid, PathLengthP1::One, Direction::South);
+// The heat loss on each block, darker where there's less, and the way the
+// crucible went
+struct Route<'a, 'g> {
+    grid: &'a Grid<'g>,
+    path: Vec<(usize, usize)>,
+}
+
+impl Render for Route<'_, '_> {
+    fn size(&self) -> (usize, usize) {
+        (self.grid.width, self.grid.height)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        let heat = self.grid.get(x, y).unwrap() as u8;
+        [heat * 25, heat * 10, 0x18]
+    }
+
+    fn paths(&self) -> Vec<(Rgb, Vec<(usize, usize)>)> {
+        vec![(visualize::WHITE, self.path.clone())]
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid<'_>, ParseError> {
+    Grid::new(input)
+}
+
+pub fn part1(grid: &Grid) -> usize {
+    let start = Pos(0, 0, grid, PathLengthP1::One, Direction::South);
     let result = pathfinding::prelude::dijkstra(
         &start,
         |p| p.successors(),
         |&Pos(x, y, ..)| x == grid.width - 1 && y == grid.height - 1,
     )
     .unwrap();
+    crate::frame!(
+        "route",
+        Route {
+            grid,
+            path: result.0.iter().map(|&Pos(x, y, ..)| (x, y)).collect()
+        }
+    );
     result.1
 }
 
-pub fn part2() -> usize {
-    let grid = Grid::new(INPUT);
-    let start = Pos(0, 0, &grid, PathLengthP2::One, Direction::East);
+pub fn part2(grid: &Grid) -> usize {
+    let start = Pos(0, 0, grid, PathLengthP2::One, Direction::East);
     let result = pathfinding::prelude::dijkstra(
         &start,
         |p| p.successors(),
         |&Pos(x, y, _, len, _)| x == grid.width - 1 && y == grid.height - 1 && len.can_turn(),
     )
     .unwrap();
+    crate::frame!(
+        "route",
+        Route {
+            grid,
+            path: result.0.iter().map(|&Pos(x, y, ..)| (x, y)).collect()
+        }
+    );
     result.1
 }
+
+// Heat loss from 1 to 9 on every block
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(141, scale);
+    let rows = generate::grid(rng, size, size, |rng, _, _| rng.gen_range(b'1'..=b'9'));
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    const EXAMPLE2: &str = "\
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day17>(EXAMPLE, 1), "102");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day17>(EXAMPLE, 2), "94");
+    }
+
+    #[test]
+    fn part2_example_2() {
+        assert_eq!(solve::<Day17>(EXAMPLE2, 2), "71");
+    }
+}
@@ -0,0 +1,350 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::cmp::Ordering;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Lavaduct Lagoon";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        for line in input.lines() {
+            check_line(input, line)?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+fn solve_points(path: impl Iterator<Item = (i64, i64)>) -> usize {
+    let mut path = path.chain(std::iter::once((0, 0)));
+    let mut area = 0;
+    let mut points_on_path = 0;
+    let mut prev = path.next().unwrap();
+    for cur in path {
+        points_on_path += {
+            #[inline(always)]
+            fn branchless_abs(a: i64) -> i64 {
+                (a + (a >> 63)) ^ (a >> 63)
+            }
+            branchless_abs((prev.0 - cur.0) ^ (prev.1 - cur.1))
+        };
+        area += (prev.0 * cur.1) - (prev.1 * cur.0);
+        prev = cur;
+    }
+    // A = i + b/2 - 1
+    // A + 1 = i + b/2
+    // A + 1 - b/2 = i
+    // Total points = A + 1 - b/2 + b
+    let b_2 = points_on_path / 2;
+    let area = area.abs() / 2;
+    let interior = area + 1 - b_2;
+    (interior + points_on_path) as usize
+}
+
+// The `Trench` closures read the input by byte offset, so make sure every
+// line looks like `R 6 (#70c710)` before they see it
+fn check_line(input: &str, line: &str) -> Result<(), ParseError> {
+    let b = line.as_bytes();
+    let error = |at: usize, expected: &str| {
+        Err(ParseError::at(
+            input,
+            line.get(at..).unwrap_or(&line[line.len()..]),
+            expected,
+        ))
+    };
+    if !matches!(b.first(), Some(b'U' | b'D' | b'L' | b'R')) {
+        return error(0, "'U', 'D', 'L' or 'R'");
+    }
+    if b.get(1) != Some(&b' ') {
+        return error(1, "' '");
+    }
+    let digits = b[2..].iter().take_while(|b| b.is_ascii_digit()).count();
+    if !(1..=2).contains(&digits) {
+        return error(2, "a one or two digit distance");
+    }
+    let colour = 2 + digits;
+    if b.get(colour..colour + 3) != Some(b" (#") {
+        return error(colour, "' (#'");
+    }
+    for i in colour + 3..colour + 8 {
+        if !matches!(b.get(i), Some(b'0'..=b'9' | b'a'..=b'f')) {
+            return error(i, "a lowercase hex digit");
+        }
+    }
+    if !matches!(b.get(colour + 8), Some(b'0'..=b'3')) {
+        return error(colour + 8, "a direction from '0' to '3'");
+    }
+    if b.get(colour + 9) != Some(&b')') {
+        return error(colour + 9, "')'");
+    }
+    if b.len() > colour + 10 {
+        return error(colour + 10, "end of line");
+    }
+    Ok(())
+}
+
+struct Trench<'a, F> {
+    input: &'a [u8],
+    position: usize,
+    location: (i64, i64),
+    f: F,
+}
+
+impl<'a, F> Trench<'a, F>
+where
+    F: FnMut(&[u8], (i64, i64)) -> ((i64, i64), usize),
+{
+    pub fn new(source: &'a str, f: F) -> Self {
+        Self {
+            input: source.as_bytes(),
+            position: 0,
+            location: (0, 0),
+            f,
+        }
+    }
+}
+
+impl<'a, F> Iterator for Trench<'a, F>
+where
+    F: FnMut(&[u8], (i64, i64)) -> ((i64, i64), usize),
+{
+    type Item = (i64, i64);
+    // perf says this saves about 15% for part 2
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.input.get(self.position..)?;
+        let ret = self.location;
+        let (new_loc, len) = (self.f)(line, ret);
+        self.position += len;
+        self.location = new_loc;
+        Some(ret)
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    let trench = Trench::new(input, |line, last| {
+        let (direction, amnt, length) = match *line {
+            [dir, _, amnt, b' ', ..] => (dir, (amnt & 0b1111) as i64, 14),
+            [dir, _, hi, lo, ..] => (dir, ((hi & 0b1111) * 10 + (lo & 0b1111)) as i64, 15),
+            _ => unreachable!(),
+        };
+        let diff = match direction {
+            b'U' => (0, -amnt),
+            b'R' => (amnt, 0),
+            b'D' => (0, amnt),
+            _ => {
+                debug_assert_eq!(direction, b'L');
+                (-amnt, 0)
+            }
+        };
+        ((last.0 + diff.0, last.1 + diff.1), length)
+    });
+    if visualize::enabled() {
+        let corners: Vec<_> = trench.collect();
+        crate::frame!("lagoon", Lagoon::new(&corners));
+        return solve_points(corners.into_iter());
+    }
+    solve_points(trench)
+}
+
+// The dig plan's outline and everything inside it
+struct Lagoon {
+    width: usize,
+    dug: Vec<bool>,
+    outline: Vec<(usize, usize)>,
+}
+
+impl Lagoon {
+    fn new(corners: &[(i64, i64)]) -> Self {
+        let min_x = corners.iter().map(|c| c.0).min().unwrap_or(0);
+        let min_y = corners.iter().map(|c| c.1).min().unwrap_or(0);
+        let mut outline: Vec<_> = corners
+            .iter()
+            .map(|&(x, y)| ((x - min_x) as usize, (y - min_y) as usize))
+            .collect();
+        outline.push(outline[0]);
+        let width = outline.iter().map(|c| c.0).max().unwrap() + 1;
+        let height = outline.iter().map(|c| c.1).max().unwrap() + 1;
+        // fill each row between pairs of the vertical edges crossing it
+        let mut dug = vec![false; width * height];
+        for y in 0..height {
+            let mut crossings: Vec<usize> = outline
+                .windows(2)
+                .filter(|e| {
+                    e[0].0 == e[1].0 && (e[0].1.min(e[1].1)..e[0].1.max(e[1].1)).contains(&y)
+                })
+                .map(|e| e[0].0)
+                .collect();
+            crossings.sort_unstable();
+            for pair in crossings.chunks_exact(2) {
+                dug[y * width + pair[0]..=y * width + pair[1]].fill(true);
+            }
+        }
+        Self {
+            width,
+            dug,
+            outline,
+        }
+    }
+}
+
+impl Render for Lagoon {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.dug.len() / self.width)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        if self.dug[y * self.width + x] {
+            [0x60, 0x40, 0x28]
+        } else {
+            visualize::BLACK
+        }
+    }
+
+    fn paths(&self) -> Vec<(Rgb, Vec<(usize, usize)>)> {
+        vec![(visualize::RED, self.outline.clone())]
+    }
+}
+
+pub fn part2(input: &str) -> usize {
+    let trench = Trench::new(input, |line, last| {
+        fn parse_hex(dir: u8, amnt: [u8; 5]) -> (u8, i64) {
+            const HEX_TABLE_LO: [u8; 256] = {
+                let mut table = [0; 256];
+                table[b'0' as usize] = 0;
+                table[b'1' as usize] = 1;
+                table[b'2' as usize] = 2;
+                table[b'3' as usize] = 3;
+                table[b'4' as usize] = 4;
+                table[b'5' as usize] = 5;
+                table[b'6' as usize] = 6;
+                table[b'7' as usize] = 7;
+                table[b'8' as usize] = 8;
+                table[b'9' as usize] = 9;
+                table[b'a' as usize] = 10;
+                table[b'b' as usize] = 11;
+                table[b'c' as usize] = 12;
+                table[b'd' as usize] = 13;
+                table[b'e' as usize] = 14;
+                table[b'f' as usize] = 15;
+                table
+            };
+            const HEX_TABLE_HI: [u8; 256] = {
+                let mut table = [0; 256];
+                table[b'0' as usize] = 0 << 4;
+                table[b'1' as usize] = 1 << 4;
+                table[b'2' as usize] = 2 << 4;
+                table[b'3' as usize] = 3 << 4;
+                table[b'4' as usize] = 4 << 4;
+                table[b'5' as usize] = 5 << 4;
+                table[b'6' as usize] = 6 << 4;
+                table[b'7' as usize] = 7 << 4;
+                table[b'8' as usize] = 8 << 4;
+                table[b'9' as usize] = 9 << 4;
+                table[b'a' as usize] = 10 << 4;
+                table[b'b' as usize] = 11 << 4;
+                table[b'c' as usize] = 12 << 4;
+                table[b'd' as usize] = 13 << 4;
+                table[b'e' as usize] = 14 << 4;
+                table[b'f' as usize] = 15 << 4;
+                table
+            };
+            let amnt = (HEX_TABLE_LO[amnt[0] as usize] as i64) << (4 * 4)
+                | (HEX_TABLE_HI[amnt[1] as usize] as i64) << (4 * 2)
+                | (HEX_TABLE_LO[amnt[2] as usize] as i64) << (4 * 2)
+                | (HEX_TABLE_HI[amnt[3] as usize] as i64)
+                | HEX_TABLE_LO[amnt[4] as usize] as i64;
+            (dir, amnt)
+        }
+        let ((direction, amnt), len) = match *line {
+            [_, _, _, b' ', _, _, a, b, c, d, e, dir, ..] => (parse_hex(dir, [a, b, c, d, e]), 14),
+            [_, _, _, _, b' ', _, _, a, b, c, d, e, dir, ..] => {
+                (parse_hex(dir, [a, b, c, d, e]), 15)
+            }
+            _ => unreachable!(),
+        };
+        let diff = match direction {
+            b'0' => (0, -amnt),
+            b'1' => (amnt, 0),
+            b'2' => (0, amnt),
+            _ => {
+                debug_assert_eq!(direction, b'3');
+                (-amnt, 0)
+            }
+        };
+        ((last.0 + diff.0, last.1 + diff.1), len)
+    });
+    solve_points(trench)
+}
+
+// The outline of a random blob, one instruction per unit of the outline.
+// Part 1 stretches each unit by a few metres, while part 2 turns the blob on
+// its side and stretches it much further.
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(60, scale);
+    let outline = generate::outline(rng, size, size);
+    let mut gaps = |max: i64| -> Vec<i64> { (0..size).map(|_| rng.gen_range(1..=max)).collect() };
+    let (short_x, short_y) = (gaps(6), gaps(6));
+    let (long_x, long_y) = (gaps(0xfffff / 4), gaps(0xfffff / 4));
+    let lines: Vec<String> = (0..outline.len())
+        .map(|i| {
+            let (x, y) = outline[i];
+            let (nx, ny) = outline[(i + 1) % outline.len()];
+            // part 2's directions are mirrored along the diagonal
+            let (dir, short, hex_dir, long) = match (nx.cmp(&x), ny.cmp(&y)) {
+                (Ordering::Greater, _) => ('R', short_x[x], 1, long_x[x]),
+                (Ordering::Less, _) => ('L', short_x[nx], 3, long_x[nx]),
+                (_, Ordering::Greater) => ('D', short_y[y], 0, long_y[y]),
+                _ => ('U', short_y[ny], 2, long_y[ny]),
+            };
+            format!("{dir} {short} (#{long:05x}{hex_dir})")
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day18>(EXAMPLE, 1), "62");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day18>(EXAMPLE, 2), "952408144115");
+    }
+}
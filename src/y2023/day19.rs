@@ -0,0 +1,450 @@
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use std::ops::Range;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Aplenty";
+    type Parsed<'a> = System<'a>;
+
+    fn parse(input: &str) -> Result<System<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(system: &System) -> Answer {
+        part1(system).into()
+    }
+
+    fn part2(system: &System) -> Answer {
+        part2(system).into()
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Part {
+    pub x: u16,
+    pub m: u16,
+    pub a: u16,
+    pub s: u16,
+}
+
+impl Part {
+    pub fn sum(&self) -> usize {
+        self.x as usize + self.m as usize + self.a as usize + self.s as usize
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WorkflowDest<'a> {
+    Reject,
+    Accept,
+    Workflow(&'a str),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Cmp {
+    Lesser,
+    Greater,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u8)]
+pub enum Prop {
+    X = b'x',
+    M = b'm',
+    A = b'a',
+    S = b's',
+}
+
+#[derive(Debug)]
+pub enum WorkflowStep<'a> {
+    Part {
+        prop: Prop,
+        cmp: Cmp,
+        value: u16,
+        dest: WorkflowDest<'a>,
+    },
+    Final(WorkflowDest<'a>),
+}
+
+#[derive(Debug)]
+pub struct Workflow<'a> {
+    pub rules: Vec<WorkflowStep<'a>>,
+}
+
+fn parse_dest(input: &str) -> WorkflowDest<'_> {
+    match input {
+        "A" => WorkflowDest::Accept,
+        "R" => WorkflowDest::Reject,
+        _ => WorkflowDest::Workflow(input),
+    }
+}
+
+fn parse_name<'a>(input: &str, name: &'a str) -> Result<&'a str, ParseError> {
+    if name.is_empty() || !name.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(ParseError::at(input, name, "a lowercase workflow name"));
+    }
+    Ok(name)
+}
+
+fn parse_value(input: &str, value: &str) -> Result<u16, ParseError> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseError::at(input, value, "a number"));
+    }
+    value
+        .parse()
+        .map_err(|_| ParseError::at(input, value, "a number below 65536"))
+}
+
+fn parse_workflow<'a>(input: &str, line: &'a str) -> Result<(&'a str, Workflow<'a>), ParseError> {
+    let Some((name, rest)) = line.split_once('{') else {
+        return Err(ParseError::at(input, &line[line.len()..], "'{'"));
+    };
+    let name = parse_name(input, name)?;
+    let Some(rest) = rest.strip_suffix('}') else {
+        return Err(ParseError::at(input, &line[line.len()..], "'}'"));
+    };
+    let rules = rest
+        .split(',')
+        .map(|step| {
+            let Some((check, dest)) = step.split_once(':') else {
+                return Ok(WorkflowStep::Final(parse_dest(step)));
+            };
+            let prop = match check.as_bytes().first() {
+                Some(b'x') => Prop::X,
+                Some(b'm') => Prop::M,
+                Some(b'a') => Prop::A,
+                Some(b's') => Prop::S,
+                _ => return Err(ParseError::at(input, check, "'x', 'm', 'a' or 's'")),
+            };
+            let cmp = match check.as_bytes().get(1) {
+                Some(b'<') => Cmp::Lesser,
+                Some(b'>') => Cmp::Greater,
+                _ => return Err(ParseError::at(input, &check[1..], "'<' or '>'")),
+            };
+            let value = parse_value(input, &check[2..])?;
+            let dest = parse_dest(dest);
+            Ok(WorkflowStep::Part {
+                prop,
+                cmp,
+                value,
+                dest,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((name, Workflow { rules }))
+}
+
+fn parse_part(input: &str, line: &str) -> Result<Part, ParseError> {
+    let Some(fields) = line.strip_prefix('{').and_then(|l| l.strip_suffix('}')) else {
+        return Err(ParseError::at(
+            input,
+            line,
+            "a part like '{x=1,m=2,a=3,s=4}'",
+        ));
+    };
+    let mut fields = fields.split(',');
+    let mut field = |name: &str| {
+        let field = fields.next().unwrap_or(&line[line.len() - 1..]);
+        match field.split_once('=') {
+            Some((n, value)) if n == name => parse_value(input, value),
+            _ => Err(ParseError::at(input, field, format!("'{name}='"))),
+        }
+    };
+    let part = Part {
+        x: field("x")?,
+        m: field("m")?,
+        a: field("a")?,
+        s: field("s")?,
+    };
+    match fields.next() {
+        Some(extra) => Err(ParseError::at(input, extra, "'}'")),
+        None => Ok(part),
+    }
+}
+
+pub struct System<'a> {
+    pub parts: Vec<Part>,
+    pub workflows: FxHashMap<&'a str, Workflow<'a>>,
+}
+
+pub fn parse(input: &str) -> Result<System<'_>, ParseError> {
+    let Some((workflows, parts)) = input.split_once("\n\n") else {
+        return Err(ParseError::at_offset(
+            input,
+            input.len(),
+            "a blank line between the workflows and the parts",
+        ));
+    };
+    let workflows: FxHashMap<_, _> = workflows
+        .lines()
+        .map(|line| parse_workflow(input, line))
+        .collect::<Result<_, _>>()?;
+    if !workflows.contains_key("in") {
+        return Err(ParseError::at(input, input, "a workflow named 'in'"));
+    }
+    // every rule has to lead somewhere
+    for workflow in workflows.values() {
+        for step in &workflow.rules {
+            let (WorkflowStep::Part { dest, .. } | WorkflowStep::Final(dest)) = step;
+            if let WorkflowDest::Workflow(name) = *dest {
+                if !workflows.contains_key(name) {
+                    return Err(ParseError::at(input, name, "'A', 'R' or a workflow name"));
+                }
+            }
+        }
+    }
+    let parts = parts
+        .lines()
+        .map(|line| parse_part(input, line))
+        .collect::<Result<_, _>>()?;
+    Ok(System { parts, workflows })
+}
+
+// return `true` if the check passes
+fn run_step(part: Part, prop: Prop, cmp: Cmp, value: u16) -> bool {
+    let part_value = match prop {
+        Prop::X => part.x,
+        Prop::M => part.m,
+        Prop::A => part.a,
+        Prop::S => part.s,
+    };
+    match cmp {
+        Cmp::Lesser => part_value < value,
+        Cmp::Greater => part_value > value,
+    }
+}
+
+pub fn part1(system: &System) -> usize {
+    let System { parts, workflows } = system;
+    parts
+        .iter()
+        .map(|&part| {
+            let mut cur = WorkflowDest::Workflow("in");
+            while let WorkflowDest::Workflow(name) = cur {
+                for step in &workflows[&name].rules {
+                    match *step {
+                        WorkflowStep::Part {
+                            prop,
+                            cmp,
+                            value,
+                            dest,
+                        } => {
+                            if run_step(part, prop, cmp, value) {
+                                cur = dest;
+                                break;
+                            }
+                        }
+                        WorkflowStep::Final(dest) => {
+                            cur = dest;
+                            break;
+                        }
+                    }
+                }
+            }
+            if matches!(cur, WorkflowDest::Accept) {
+                part.sum()
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+#[derive(Clone, Debug)]
+struct SymbolicPart {
+    x: Range<u16>,
+    m: Range<u16>,
+    a: Range<u16>,
+    s: Range<u16>,
+}
+
+impl SymbolicPart {
+    pub fn count(&self) -> usize {
+        self.x.len() * self.m.len() * self.a.len() * self.s.len()
+    }
+}
+
+// first range is the matching range, second is the non-matching one, either
+// of which can be empty
+fn split_range(range: &Range<u16>, cmp: Cmp, value: u16) -> (Range<u16>, Range<u16>) {
+    match cmp {
+        // (1..100),  < 5
+        Cmp::Lesser => {
+            let split = value.clamp(range.start, range.end);
+            (range.start..split, split..range.end)
+        }
+        // (1..100),  > 5
+        Cmp::Greater => {
+            let split = value.saturating_add(1).clamp(range.start, range.end);
+            (split..range.end, range.start..split)
+        }
+    }
+}
+
+pub fn part2(system: &System) -> usize {
+    let workflows = &system.workflows;
+    // stack of a workflow, the current step we're on, and the symbolic part
+    // before being constrained by this step
+    let mut stack = vec![(
+        &workflows["in"],
+        0,
+        SymbolicPart {
+            x: 1..4001,
+            m: 1..4001,
+            a: 1..4001,
+            s: 1..4001,
+        },
+    )];
+    let mut sum = 0;
+    'stack: while let Some((mut wf, mut step, mut part)) = stack.pop() {
+        while let WorkflowStep::Final(dest) = wf.rules[step] {
+            match dest {
+                WorkflowDest::Reject => continue 'stack,
+                WorkflowDest::Accept => {
+                    sum += part.count();
+                    continue 'stack;
+                }
+                WorkflowDest::Workflow(dest) => {
+                    wf = &workflows[dest];
+                    step = 0;
+                }
+            }
+        }
+        while let WorkflowStep::Part {
+            prop,
+            cmp,
+            value,
+            dest,
+        } = wf.rules[step]
+        {
+            let mut part2 = part.clone();
+            let field = match prop {
+                Prop::X => &mut part.x,
+                Prop::M => &mut part.m,
+                Prop::A => &mut part.a,
+                Prop::S => &mut part.s,
+            };
+            let (r1, r2) = split_range(field, cmp, value);
+            *match prop {
+                Prop::X => &mut part2.x,
+                Prop::M => &mut part2.m,
+                Prop::A => &mut part2.a,
+                Prop::S => &mut part2.s,
+            } = r1;
+            match dest {
+                WorkflowDest::Reject => (),
+                WorkflowDest::Accept => sum += part2.count(),
+                WorkflowDest::Workflow(dest) => stack.push((&workflows[&dest], 0, part2)),
+            }
+            *field = r2;
+            step += 1;
+        }
+        let WorkflowStep::Final(dest) = wf.rules[step] else {
+            unreachable!()
+        };
+        match dest {
+            WorkflowDest::Reject => continue 'stack,
+            WorkflowDest::Accept => {
+                sum += part.count();
+                continue 'stack;
+            }
+            WorkflowDest::Workflow(dest) => {
+                stack.push((&workflows[dest], 0, part));
+            }
+        }
+    }
+    sum
+}
+
+// A tree of workflows grown out from `in`, so every part ends up accepted
+// or rejected
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    // names are two or three letters, and there aren't that many of those
+    let target = (550 * scale).min(15_000);
+    let mut used = FxHashSet::default();
+    used.insert("in".to_string());
+    let mut pending = VecDeque::from(["in".to_string()]);
+    let mut workflows = Vec::new();
+    while let Some(name) = pending.pop_front() {
+        let mut dest = |rng: &mut StdRng| {
+            if used.len() < target && rng.gen_bool(0.7) {
+                let name = loop {
+                    let name: String = (0..rng.gen_range(2..=3))
+                        .map(|_| rng.gen_range('a'..='z'))
+                        .collect();
+                    if used.insert(name.clone()) {
+                        break name;
+                    }
+                };
+                pending.push_back(name.clone());
+                name
+            } else {
+                ["A", "R"].choose(rng).unwrap().to_string()
+            }
+        };
+        let mut rules: Vec<String> = (0..rng.gen_range(1..=3))
+            .map(|_| {
+                format!(
+                    "{}{}{}:{}",
+                    *b"xmas".choose(rng).unwrap() as char,
+                    *b"<>".choose(rng).unwrap() as char,
+                    rng.gen_range(1..=4000),
+                    dest(rng)
+                )
+            })
+            .collect();
+        rules.push(dest(rng));
+        workflows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    workflows.shuffle(rng);
+    let parts: Vec<String> = (0..200 * scale)
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .collect();
+    format!("{}\n\n{}", workflows.join("\n"), parts.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day19>(EXAMPLE, 1), "19114");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day19>(EXAMPLE, 2), "167409079868000");
+    }
+}
@@ -0,0 +1,191 @@
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+    type Parsed<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(games: &Vec<Game>) -> Answer {
+        part1(games).into()
+    }
+
+    fn part2(games: &Vec<Game>) -> Answer {
+        part2(games).into()
+    }
+}
+
+// The number of (red, green, blue) cubes in each pull
+pub type Game = Vec<(u8, u8, u8)>;
+
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = format!("Game {}: ", i + 1);
+            let Some(pulls) = line.strip_prefix(&prefix) else {
+                return Err(ParseError::at(input, line, format!("'{prefix}'")));
+            };
+            Pulls::new(input, pulls).collect()
+        })
+        .collect()
+}
+
+pub fn part1(games: &[Game]) -> usize {
+    games
+        .iter()
+        .enumerate()
+        .filter(|(_, game)| game.iter().all(|&(r, g, b)| r <= 12 && g <= 13 && b <= 14))
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+pub fn part2(games: &[Game]) -> usize {
+    games
+        .iter()
+        .map(|game| {
+            let (red, green, blue) = game
+                .iter()
+                .fold((0, 0, 0), |(r, g, b), &(red, green, blue)| {
+                    (r.max(red), g.max(green), b.max(blue))
+                });
+            red as usize * green as usize * blue as usize
+        })
+        .sum()
+}
+
+struct Pulls<'a> {
+    input: &'a str,
+    line: &'a str,
+    pos: usize,
+}
+
+// Takes a list of pulls and returns the number of (red, green, blue) cubes in each
+impl<'a> Pulls<'a> {
+    fn new(input: &'a str, line: &'a str) -> Self {
+        Self {
+            input,
+            line,
+            pos: 0,
+        }
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        ParseError::at(self.input, &self.line[self.pos..], expected)
+    }
+
+    fn next_pull(&mut self) -> Result<(u8, u8, u8), ParseError> {
+        let src = self.line.as_bytes();
+        let mut red = 0;
+        let mut green = 0;
+        let mut blue = 0;
+        loop {
+            let n = match src[self.pos..] {
+                // single digit
+                [lo @ b'0'..=b'9', b' ', ..] => {
+                    self.pos += 2;
+                    lo - b'0'
+                }
+                // double digit
+                [hi @ b'0'..=b'9', lo @ b'0'..=b'9', b' ', ..] => {
+                    self.pos += 3;
+                    (hi - b'0') * 10 + (lo - b'0')
+                }
+                _ => return Err(self.error("a number of cubes")),
+            };
+            let rest = &src[self.pos..];
+            if rest.starts_with(b"red") {
+                red = n;
+                self.pos += 3;
+            } else if rest.starts_with(b"green") {
+                green = n;
+                self.pos += 5;
+            } else if rest.starts_with(b"blue") {
+                blue = n;
+                self.pos += 4;
+            } else {
+                return Err(self.error("'red', 'green' or 'blue'"));
+            }
+            match src[self.pos..] {
+                [b',', b' ', ..] => self.pos += 2,
+                // end of pull
+                [b';', b' ', ..] => {
+                    self.pos += 2;
+                    return Ok((red, green, blue));
+                }
+                // end of line
+                [] => return Ok((red, green, blue)),
+                _ => return Err(self.error("',', ';' or end of line")),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Pulls<'a> {
+    type Item = Result<(u8, u8, u8), ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.line.len() {
+            return None;
+        }
+        let pull = self.next_pull();
+        if pull.is_err() {
+            // stop after the first error
+            self.pos = self.line.len();
+        }
+        Some(pull)
+    }
+}
+
+// Games of a few pulls, with up to 20 cubes of each colour
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let games: Vec<String> = (1..=100 * scale)
+        .map(|i| {
+            let pulls: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colours = ["red", "green", "blue"];
+                    colours.shuffle(rng);
+                    let cubes: Vec<String> = colours[..rng.gen_range(1..=3)]
+                        .iter()
+                        .map(|colour| format!("{} {colour}", rng.gen_range(1..=20)))
+                        .collect();
+                    cubes.join(", ")
+                })
+                .collect();
+            format!("Game {i}: {}", pulls.join("; "))
+        })
+        .collect();
+    games.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day2>(EXAMPLE, 1), "8");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day2>(EXAMPLE, 2), "2286");
+    }
+}
@@ -0,0 +1,465 @@
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use crate::visualize::{self, Canvas};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rustc_hash::FxHashMap;
+use std::{
+    cell::{Cell, OnceCell, RefCell},
+    collections::VecDeque,
+};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Pulse Propagation";
+    type Parsed<'a> = Circuit;
+
+    fn parse(input: &str) -> Result<Circuit, ParseError> {
+        parse(input)
+    }
+
+    fn part1(circuit: &Circuit) -> Answer {
+        part1(circuit).into()
+    }
+
+    fn part2(circuit: &Circuit) -> Answer {
+        part2(circuit).into()
+    }
+}
+
+pub type ModuleKey = u16;
+
+pub struct Circuit {
+    map: Box<[Option<Module>; u16::MAX as usize + 1]>,
+    signals: RefCell<VecDeque<(ModuleKey, ModuleKey, bool)>>,
+}
+
+impl Circuit {
+    pub fn get(&self, name: ModuleKey) -> Option<&Module> {
+        self.map[name as usize].as_ref()
+    }
+
+    pub fn send_signal(&self, from: ModuleKey, to: ModuleKey, signal: bool) {
+        self.signals.borrow_mut().push_back((from, to, signal));
+    }
+
+    pub fn process<F: FnMut(ModuleKey, ModuleKey, bool)>(&self, mut f: F) {
+        let mut longest = 0;
+        loop {
+            let Some((from, to, signal)) = self.signals.borrow_mut().pop_front() else {
+                break;
+            };
+            longest = longest.max(self.signals.borrow().len());
+            f(from, to, signal);
+            match self.get(to).map(|m| (m, &m.kind)) {
+                None => {
+                    debug_assert_eq!(to, RX);
+                }
+                Some((_, ModuleKind::FlipFlop { .. })) if signal => {}
+                Some((to, ModuleKind::FlipFlop { ref on })) => {
+                    if on.get() {
+                        on.set(false);
+                        self.signals
+                            .borrow_mut()
+                            .extend(to.targets.iter().map(|t: &u16| (to.name, *t, false)));
+                    } else {
+                        on.set(true);
+                        self.signals
+                            .borrow_mut()
+                            .extend(to.targets.iter().map(|t| (to.name, *t, true)));
+                    }
+                }
+                Some((to, ModuleKind::Broadcaster)) => {
+                    self.signals
+                        .borrow_mut()
+                        .extend(to.targets.iter().map(|t| (to.name, *t, false)));
+                }
+                Some((to, ModuleKind::Conjunction { ref memory })) => {
+                    memory
+                        .borrow()
+                        .iter()
+                        .find(|(k, _)| *k == from)
+                        .unwrap()
+                        .1
+                        .set(signal);
+                    if memory.borrow().iter().all(|v| v.1.get()) {
+                        self.signals
+                            .borrow_mut()
+                            .extend(to.targets.iter().map(|t| (to.name, *t, false)));
+                    } else {
+                        self.signals
+                            .borrow_mut()
+                            .extend(to.targets.iter().map(|t| (to.name, *t, true)));
+                    }
+                }
+            }
+            crate::step!("pulses", self.snapshot((from, to, signal)));
+        }
+    }
+
+    // Every module in a square, lit up by its state, with the one `pulse` just
+    // went to picked out
+    fn snapshot(&self, (from, to, signal): (ModuleKey, ModuleKey, bool)) -> Canvas {
+        let modules: Vec<_> = self.iter().collect();
+        let columns = (modules.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let rows = modules.len().div_ceil(columns);
+        // a gap between each
+        let mut canvas = Canvas::new(columns * 2 - 1, (rows * 2).max(2) - 1);
+        for (i, (key, module)) in modules.into_iter().enumerate() {
+            let colour = match &module.kind {
+                _ if key == to && signal => visualize::WHITE,
+                _ if key == to => visualize::RED,
+                ModuleKind::Broadcaster => visualize::BLUE,
+                ModuleKind::FlipFlop { on } if on.get() => visualize::YELLOW,
+                ModuleKind::FlipFlop { .. } => [0x50, 0x48, 0x10],
+                ModuleKind::Conjunction { memory } if memory.borrow().iter().all(|m| m.1.get()) => {
+                    visualize::GREEN
+                }
+                ModuleKind::Conjunction { .. } => [0x18, 0x48, 0x20],
+            };
+            canvas.set(i % columns * 2, i / columns * 2, colour);
+        }
+        canvas.status = format!(
+            "{} -{}-> {}, {} more to send",
+            key_name(from),
+            if signal { "high" } else { "low" },
+            key_name(to),
+            self.signals.borrow().len()
+        );
+        canvas
+    }
+
+    // Turn every flip-flop off and forget every remembered pulse, so a parsed
+    // circuit can be pushed from its initial state again
+    pub fn reset(&self) {
+        for (_, module) in self.iter() {
+            match &module.kind {
+                ModuleKind::Broadcaster => {}
+                ModuleKind::FlipFlop { on } => on.set(false),
+                ModuleKind::Conjunction { memory } => {
+                    memory.borrow().iter().for_each(|(_, m)| m.set(false))
+                }
+            }
+        }
+        self.signals.borrow_mut().clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &Module)> {
+        self.map
+            .iter()
+            .enumerate()
+            .filter_map(|(i, module)| module.as_ref().map(|m| (i, m)))
+            .take(MAX_MODULES)
+            .map(|(i, md)| (i as u16, md))
+    }
+}
+
+#[derive(Debug)]
+pub struct Module {
+    pub kind: ModuleKind,
+    pub name: ModuleKey,
+    pub targets: Vec<ModuleKey>,
+}
+
+#[derive(Debug)]
+pub enum ModuleKind {
+    Broadcaster,
+    FlipFlop {
+        on: Cell<bool>,
+    },
+    // Map of input modules to their most recent signal
+    Conjunction {
+        memory: RefCell<Vec<(ModuleKey, Cell<bool>)>>,
+    },
+}
+
+pub const fn name_to_key(name: &str) -> ModuleKey {
+    match name.as_bytes() {
+        &[hi, lo] => u16::from_be_bytes([hi, lo]),
+
+        #[cfg(debug_assertions)]
+        b"broadcaster" => BROADCASTER,
+        #[cfg(debug_assertions)]
+        _ => unreachable!(),
+
+        #[cfg(not(debug_assertions))]
+        _ => BROADCASTER,
+    }
+}
+
+fn key_name(key: ModuleKey) -> String {
+    match key {
+        BROADCASTER => "broadcaster".to_string(),
+        BTN => "button".to_string(),
+        _ => String::from_utf8_lossy(&key.to_be_bytes()).into_owned(),
+    }
+}
+
+fn parse_key(input: &str, name: &str) -> Result<ModuleKey, ParseError> {
+    match name.as_bytes() {
+        b"broadcaster" => Ok(BROADCASTER),
+        [b'a'..=b'z', b'a'..=b'z'] => Ok(name_to_key(name)),
+        _ => Err(ParseError::at(
+            input,
+            name,
+            "a two letter module name or 'broadcaster'",
+        )),
+    }
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Module, ParseError> {
+    let Some((name, stuff)) = line.split_once(" -> ") else {
+        return Err(ParseError::at(input, &line[line.len()..], "' -> '"));
+    };
+    let targets = stuff
+        .split(", ")
+        .filter(|s| !s.is_empty())
+        .map(|target| parse_key(input, target))
+        .collect::<Result<_, _>>()?;
+
+    let (kind, name) = if let Some(name) = name.strip_prefix('%') {
+        (
+            ModuleKind::FlipFlop {
+                on: Cell::new(false),
+            },
+            parse_key(input, name)?,
+        )
+    } else if let Some(name) = name.strip_prefix('&') {
+        (
+            ModuleKind::Conjunction {
+                memory: RefCell::new(Vec::with_capacity(4)),
+            },
+            parse_key(input, name)?,
+        )
+    } else if name == "broadcaster" {
+        (ModuleKind::Broadcaster, BROADCASTER)
+    } else {
+        return Err(ParseError::at(input, name, "'%', '&' or 'broadcaster'"));
+    };
+    Ok(Module {
+        kind,
+        name,
+        targets,
+    })
+}
+
+const RX: ModuleKey = name_to_key("rx");
+
+// `Circuit::iter` only looks this far
+const MAX_MODULES: usize = 100;
+
+pub fn parse(input: &str) -> Result<Circuit, ParseError> {
+    // built on the heap, as the array is too big for the stack in debug builds
+    let mut map: Box<[Option<Module>; u16::MAX as usize + 1]> = std::iter::repeat_with(|| None)
+        .take(u16::MAX as usize + 1)
+        .collect::<Box<[_]>>()
+        .try_into()
+        .unwrap();
+    for (i, line) in input.lines().enumerate() {
+        if i == MAX_MODULES {
+            return Err(ParseError::at(input, line, "at most 100 modules"));
+        }
+        let module = parse_line(input, line)?;
+        let name = module.name;
+        map[name as usize] = Some(module);
+    }
+    if map[BROADCASTER as usize].is_none() {
+        return Err(ParseError::at_offset(
+            input,
+            input.len(),
+            "a 'broadcaster' module",
+        ));
+    }
+    // pulses can only leave the circuit through rx
+    for line in input.lines() {
+        let targets = line.split_once(" -> ").map_or("", |(_, t)| t);
+        for target in targets.split(", ").filter(|t| !t.is_empty()) {
+            if target != "rx" && map[name_to_key(target) as usize].is_none() {
+                return Err(ParseError::at(
+                    input,
+                    target,
+                    "a module defined in the input",
+                ));
+            }
+        }
+    }
+
+    for (name, module) in map
+        .iter()
+        .enumerate()
+        .filter_map(|(i, module)| module.as_ref().map(|m| (i, m)))
+        .take(MAX_MODULES)
+    {
+        for tgt in module.targets.iter() {
+            if let Some(Some(tgt)) = map.get(*tgt as usize) {
+                if let ModuleKind::Conjunction { memory } = &tgt.kind {
+                    memory
+                        .borrow_mut()
+                        .push((name as ModuleKey, Cell::new(false)));
+                }
+            }
+        }
+    }
+    Ok(Circuit {
+        map,
+        signals: RefCell::new(VecDeque::with_capacity(32)),
+    })
+}
+
+const BTN: ModuleKey = ModuleKey::MAX - 1;
+const BROADCASTER: ModuleKey = ModuleKey::MAX;
+
+pub fn part1(circuit: &Circuit) -> usize {
+    circuit.reset();
+    let mut lo_count = 0;
+    let mut hi_count = 0;
+    let broadcast = circuit.get(BROADCASTER);
+    for _ in 0..1000 {
+        // lo_count += 1;
+        circuit.send_signal(BTN, BROADCASTER, false);
+        circuit.process(|_, _, signal| {
+            if signal {
+                hi_count += 1;
+            } else {
+                lo_count += 1;
+            }
+        });
+    }
+
+    lo_count * hi_count
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    fn gcd(mut a: usize, mut b: usize) -> usize {
+        while b != 0 {
+            let temp = b;
+            b = a % b;
+            a = temp;
+        }
+        a
+    }
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a * b / gcd(a, b)
+    }
+}
+
+pub fn part2(circuit: &Circuit) -> usize {
+    circuit.reset();
+    let before_rx = circuit
+        .iter()
+        .map(|(_, v)| v)
+        .find(|module| module.targets.contains(&RX))
+        .unwrap();
+    let ModuleKind::Conjunction { memory } = &before_rx.kind else {
+        unreachable!()
+    };
+
+    let before_before_rx: FxHashMap<_, _> = memory
+        .borrow()
+        .iter()
+        .map(|k| (k.0, OnceCell::new()))
+        .collect();
+
+    let mut cycle_count = 0;
+    let mut i = 0;
+
+    while cycle_count < before_before_rx.len() {
+        i += 1;
+        circuit.send_signal(BTN, BROADCASTER, false);
+        circuit.process(|from, to, signal| {
+            if signal && to == before_rx.name {
+                let entry = &before_before_rx[&from];
+                if entry.set(i).is_ok() {
+                    cycle_count += 1;
+                }
+            }
+        });
+    }
+    before_before_rx.iter().fold(1, |acc, (key, val)| {
+        let i = *val.get().unwrap();
+        lcm(acc, i)
+    })
+}
+
+// The same shape as the real circuit: four twelve bit counters, each
+// resetting when it reaches a different prime, which is when it sends a
+// high pulse on towards rx. Any more counters and the answer wouldn't fit
+// in a u64, so the scale is ignored.
+pub fn generate(rng: &mut StdRng, _scale: usize) -> String {
+    const COUNTERS: usize = 4;
+    const BITS: usize = 12;
+    let mut names: Vec<String> = (b'a'..=b'z')
+        .flat_map(|a| (b'a'..=b'z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .filter(|name| name != "rx")
+        .collect();
+    names.shuffle(rng);
+    let mut names = names.into_iter();
+    let mut primes: Vec<usize> = (1 << (BITS - 1)..1 << BITS)
+        .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect();
+    primes.shuffle(rng);
+
+    let last = names.next().unwrap();
+    let mut lines = vec![format!("&{last} -> rx")];
+    let mut firsts = Vec::new();
+    for &prime in &primes[..COUNTERS] {
+        let bits: Vec<String> = names.by_ref().take(BITS).collect();
+        let (hub, inverter) = (names.next().unwrap(), names.next().unwrap());
+        // the hub resets the bits that are off in `prime`, and the first
+        // bit, which rolls the counter over to zero
+        let mut resets = vec![bits[0].clone()];
+        for (i, bit) in bits.iter().enumerate() {
+            let mut targets = Vec::new();
+            if let Some(next) = bits.get(i + 1) {
+                targets.push(next.clone());
+            }
+            if prime & (1 << i) != 0 {
+                targets.push(hub.clone());
+            } else {
+                resets.push(bit.clone());
+            }
+            targets.shuffle(rng);
+            lines.push(format!("%{bit} -> {}", targets.join(", ")));
+        }
+        resets.push(inverter.clone());
+        resets.shuffle(rng);
+        lines.push(format!("&{hub} -> {}", resets.join(", ")));
+        lines.push(format!("&{inverter} -> {last}"));
+        firsts.push(bits[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", firsts.join(", ")));
+    lines.shuffle(rng);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    // module names have to be two letters, so the examples are relabelled,
+    // with `output` becoming `rx`
+    const EXAMPLE: &str = "\
+broadcaster -> aa, bb, cc
+%aa -> bb
+%bb -> cc
+%cc -> iv
+&iv -> aa";
+
+    const EXAMPLE2: &str = "\
+broadcaster -> aa
+%aa -> iv, cn
+&iv -> bb
+%bb -> cn
+&cn -> rx";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day20>(EXAMPLE, 1), "32000000");
+        assert_eq!(solve::<Day20>(EXAMPLE2, 1), "11687500");
+    }
+}
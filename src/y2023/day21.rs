@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::Rng;
+use rustc_hash::FxHashMap;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Step Counter";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        check_grid(input, input, "'.', '#' or 'S'", |b| b".#S".contains(&b))?;
+        let mut starts = input.match_indices('S').map(|(i, _)| i);
+        if starts.next().is_none() {
+            return Err(ParseError::at_offset(
+                input,
+                input.len(),
+                "an 'S' to start from",
+            ));
+        }
+        if let Some(i) = starts.next() {
+            return Err(ParseError::at_offset(input, i, "only one 'S'"));
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Pos(usize, u32);
+
+const N_STEPS: u32 = 64;
+
+impl Pos {
+    pub fn successors(self, grid: &[u8], width: usize) -> impl Iterator<Item = Self> + '_ {
+        let w = width as isize;
+        [-1, 1, -w, w].into_iter().filter_map(move |diff| {
+            let pos = self.0.checked_add_signed(diff)?;
+            match grid.get(pos)? {
+                b'.' | b'S' if self.1 < 1024 => Some(Pos(pos, self.1 + 1)),
+                _ => None,
+            }
+        })
+    }
+}
+
+fn get_reachable(input: &str, start: usize) -> impl Iterator<Item = Pos> + '_ {
+    let input = input.as_bytes();
+    let width = input
+        .iter()
+        .position(|&b| b == b'\n')
+        .unwrap_or(input.len())
+        + 1;
+    let mut queue = VecDeque::with_capacity(2048);
+    let mut visited = FxHashMap::with_capacity_and_hasher(input.len(), Default::default());
+    queue.push_back(Pos(start, 0));
+    std::iter::from_fn(move || {
+        let pos = loop {
+            let p = queue.pop_front()?;
+            if !visited.contains_key(&p.0) {
+                break p;
+            }
+        };
+        crate::trace!("visiting {pos:?}");
+        visited.insert(pos.0, pos.1);
+        let w = width as isize;
+        for diff in [-1, 1, -w, w] {
+            let Some(np) = pos.0.checked_add_signed(diff) else {
+                continue;
+            };
+            crate::trace!("{np} - {:?}", input.get(np));
+            if let Some(b'.' | b'S') = input.get(np) {
+                crate::trace!("adding Pos({np}, {})", pos.1 + 1);
+                queue.push_back(Pos(np, pos.1 + 1));
+            }
+        }
+        Some(pos)
+    })
+}
+
+// Plots that can be ended on after exactly `steps` steps from `start`
+fn count_reachable(input: &str, start: usize, steps: u32) -> usize {
+    get_reachable(input, start)
+        .filter(|p| p.1 <= steps && p.1 % 2 == steps % 2)
+        .count()
+}
+
+pub fn part1(input: &str) -> usize {
+    let start = input.find('S').unwrap();
+    if visualize::enabled() {
+        let distances: FxHashMap<_, _> = get_reachable(input, start)
+            .take_while(|p| p.1 <= N_STEPS)
+            .map(|p| (p.0, p.1))
+            .collect();
+        for step in 0..=N_STEPS {
+            crate::frame!(
+                "steps",
+                Garden {
+                    input: input.as_bytes(),
+                    distances: &distances,
+                    step
+                }
+            );
+        }
+    }
+    count_reachable(input, start, N_STEPS)
+}
+
+// Where the elf could be after `step` steps, brighter for the plots they
+// could finish on
+struct Garden<'a> {
+    input: &'a [u8],
+    distances: &'a FxHashMap<usize, u32>,
+    step: u32,
+}
+
+impl Render for Garden<'_> {
+    fn size(&self) -> (usize, usize) {
+        let width = self
+            .input
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(self.input.len())
+            + 1;
+        (width - 1, self.input.len().div_ceil(width))
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        let i = y * (self.size().0 + 1) + x;
+        match self.distances.get(&i) {
+            Some(&d) if d <= self.step && d % 2 == self.step % 2 => visualize::GREEN,
+            Some(&d) if d <= self.step => [0x20, 0x50, 0x28],
+            _ if self.input[i] == b'#' => visualize::GREY,
+            _ => visualize::BLACK,
+        }
+    }
+}
+
+pub fn part2(input: &str) -> usize {
+    // 308618359186200 too low
+    // 637535765988470 too low
+    count_tiled(input, 202300)
+}
+
+// Plots that can be ended on after exactly `size / 2 + size * n` steps with
+// the garden repeating forever, for even `n`. The middle row and column and
+// the edges are clear, so each garden is entered at the middle of an edge or
+// a corner, and the gardens reached form a diamond.
+pub(crate) fn count_tiled(input: &str, n: usize) -> usize {
+    let size = input.find('\n').unwrap_or(input.len());
+    let (mid, last) = (size / 2, size - 1);
+    let from =
+        |x: usize, y: usize, steps: usize| count_reachable(input, y * (size + 1) + x, steps as u32);
+    // whole gardens inside the diamond, alternating parity outwards: (n - 1)²
+    // like the start's and n² the other way
+    let whole = (n - 1).pow(2) * from(mid, mid, 2 * size + 1) + n.pow(2) * from(mid, mid, 2 * size);
+    // its four points, entered from the middle of the edge facing the start
+    let points =
+        from(mid, last, last) + from(mid, 0, last) + from(last, mid, last) + from(0, mid, last);
+    // along each side, n gardens with just the nearest corner reached, and
+    // n - 1 with all but the furthest one
+    let corners = [(0, 0), (last, 0), (0, last), (last, last)];
+    let small: usize = corners.iter().map(|&(x, y)| from(x, y, mid - 1)).sum();
+    let large: usize = corners
+        .iter()
+        .map(|&(x, y)| from(x, y, size + mid - 1))
+        .sum();
+    whole + points + n * small + (n - 1) * large
+}
+
+// The real garden's layout: 131 square, with the start in the middle, and
+// the edges, middle row and column, and a diamond between them clear of
+// rocks. Part 2's geometry relies on all of that, so the scale is ignored.
+pub fn generate(rng: &mut StdRng, _scale: usize) -> String {
+    const SIZE: usize = 131;
+    const MID: usize = SIZE / 2;
+    let rows = generate::grid(rng, SIZE, SIZE, |rng, x, y| {
+        let distance = x.abs_diff(MID) + y.abs_diff(MID);
+        if (x, y) == (MID, MID) {
+            b'S'
+        } else if x == MID
+            || y == MID
+            || x % (SIZE - 1) == 0
+            || y % (SIZE - 1) == 0
+            || (MID - 3..=MID + 3).contains(&distance)
+            || !rng.gen_bool(0.15)
+        {
+            b'.'
+        } else {
+            b'#'
+        }
+    });
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(count_reachable(EXAMPLE, EXAMPLE.find('S').unwrap(), 6), 16);
+    }
+}
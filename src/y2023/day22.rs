@@ -5,30 +5,43 @@ use std::{
     rc::Rc,
 };
 
+use crate::answer::Answer;
+use crate::solution::{parse_number, ParseError, Solution};
+use crate::visualize::{self, Canvas};
+use rand::rngs::StdRng;
+use rand::Rng;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-/*const INPUT: &str = "\
-1,0,1~1,2,1
-0,0,2~2,0,2
-0,2,3~2,2,3
-0,0,4~0,2,4
-2,0,5~2,2,5
-0,1,6~2,1,6
-1,1,8~1,1,9";
-*/
+pub struct Day22;
 
-const INPUT: &str = include_str!("inputs/22.txt");
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Sand Slabs";
+    type Parsed<'a> = Settled;
+
+    fn parse(input: &str) -> Result<Settled, ParseError> {
+        parse(input)
+    }
+
+    fn part1(settled: &Settled) -> Answer {
+        part1(settled).into()
+    }
+
+    fn part2(settled: &Settled) -> Answer {
+        part2(settled).into()
+    }
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Pos {
-    x: u32,
-    y: u32,
+pub struct Pos {
+    pub x: u32,
+    pub y: u32,
     // height
-    z: NonZeroU32,
+    pub z: NonZeroU32,
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Brick(Pos, Pos);
+pub struct Brick(pub Pos, pub Pos);
 
 enum Orientation {
     // X differs
@@ -46,7 +59,7 @@ impl Brick {
         points.sort_unstable();
         let [cur, end] = points;
         let mut cur = Some(cur);
-        // println!("getting points of brick {self:?}");
+        crate::trace!("getting points of brick {self:?}");
         std::iter::from_fn(move || {
             let ret = cur?;
             let next = if ret.x < end.x {
@@ -91,9 +104,9 @@ impl Brick {
     pub fn supports(&self, other: &Brick) -> bool {
         use Orientation::*;
         // Top must be exactly 1 below bottom of other
-        // println!("does {self:?} support {other:?}");
+        crate::trace!("does {self:?} support {other:?}");
         if self.1.z.get() != other.0.z.get() - 1 {
-            // println!("no - z");
+            crate::trace!("no - z");
             return false;
         }
         let self_x = (self.0.x..=self.1.x);
@@ -127,7 +140,7 @@ impl Brick {
                 self.0.x == other.0.x && (range_overlap(&self_y, &other_y))
             }
         };
-        // println!("{r}");
+        crate::trace!("{r}");
         r
     }
 
@@ -146,30 +159,55 @@ impl Brick {
     }
 }
 
-fn parse(input: &str) -> Vec<Brick> {
-    INPUT
-        .lines()
-        .map(|l| {
-            let (a, b) = l.split_once('~').unwrap();
-            let mut a = a.split(',').map(|n| n.parse().unwrap());
-            let mut b = b.split(',').map(|n| n.parse().unwrap());
-            Brick(
-                Pos {
-                    x: a.next().unwrap(),
-                    y: a.next().unwrap(),
-                    z: NonZeroU32::new(a.next().unwrap()).unwrap(),
-                },
-                Pos {
-                    x: b.next().unwrap(),
-                    y: b.next().unwrap(),
-                    z: NonZeroU32::new(b.next().unwrap()).unwrap(),
-                },
-            )
-        })
-        .collect()
+// One end of a brick, like `1,0,1`
+fn parse_pos(input: &str, end: &str) -> Result<Pos, ParseError> {
+    let coords: Vec<&str> = end.split(',').collect();
+    if coords.len() < 3 {
+        return Err(ParseError::at(
+            input,
+            &end[end.len()..],
+            "',' and another coordinate",
+        ));
+    }
+    if coords.len() > 3 {
+        let (third, _) = end.match_indices(',').nth(2).unwrap();
+        return Err(ParseError::at(input, &end[third..], "'~' or end of line"));
+    }
+    let z = parse_number(input, coords[2])?;
+    Ok(Pos {
+        x: parse_number(input, coords[0])?,
+        y: parse_number(input, coords[1])?,
+        z: NonZeroU32::new(z)
+            .ok_or_else(|| ParseError::at(input, coords[2], "a height of at least 1"))?,
+    })
+}
+
+// A brick like `1,0,1~1,2,1`, which has to run in a straight line from the
+// first end to the second
+fn parse_brick(input: &str, line: &str) -> Result<Brick, ParseError> {
+    let Some((a, b)) = line.split_once('~') else {
+        return Err(ParseError::at(input, &line[line.len()..], "'~'"));
+    };
+    let (a, b) = (parse_pos(input, a)?, parse_pos(input, b)?);
+    if a.x > b.x || a.y > b.y || a.z > b.z {
+        return Err(ParseError::at(
+            input,
+            line,
+            "a second end at or beyond the first",
+        ));
+    }
+    let differ = [a.x != b.x, a.y != b.y, a.z != b.z];
+    if differ.into_iter().filter(|&d| d).count() > 1 {
+        return Err(ParseError::at(input, line, "a brick one cube wide"));
+    }
+    Ok(Brick(a, b))
 }
 
-fn simulate_bricks(mut bricks: Vec<Brick>) -> Vec<Brick> {
+pub fn parse_bricks(input: &str) -> Result<Vec<Brick>, ParseError> {
+    input.lines().map(|l| parse_brick(input, l)).collect()
+}
+
+pub fn simulate_bricks(mut bricks: Vec<Brick>) -> Vec<Brick> {
     bricks.sort_unstable_by_key(|b| {
         debug_assert!(b.0 <= b.1, "{b:?}");
         b.0.z
@@ -200,11 +238,31 @@ fn simulate_bricks(mut bricks: Vec<Brick>) -> Vec<Brick> {
     }
 
     while !bricks.is_empty() {
-        do_fall(&mut bricks, &mut fallen)
+        do_fall(&mut bricks, &mut fallen);
+        crate::step!("falling", side_view(&bricks, &fallen));
     }
     fallen
 }
 
+// The stack from the front, looking along y, next to the stack from the
+// side, looking along x, with the bricks still falling picked out
+fn side_view(falling: &[Brick], fallen: &[Brick]) -> Canvas {
+    let all = || falling.iter().chain(fallen);
+    let max_x = all().map(|b| b.0.x.max(b.1.x)).max().unwrap_or(0) as usize;
+    let max_y = all().map(|b| b.0.y.max(b.1.y)).max().unwrap_or(0) as usize;
+    let max_z = all().map(|b| b.0.z.max(b.1.z).get()).max().unwrap_or(1) as usize;
+    let mut canvas = Canvas::new(max_x + max_y + 3, max_z);
+    for (bricks, colour) in [(fallen, visualize::GREY), (falling, visualize::ORANGE)] {
+        for p in bricks.iter().flat_map(Brick::points) {
+            let row = max_z - p.z.get() as usize;
+            canvas.set(p.x as usize, row, colour);
+            canvas.set(max_x + 2 + p.y as usize, row, colour);
+        }
+    }
+    canvas.status = format!("{} settled, {} falling", fallen.len(), falling.len());
+    canvas
+}
+
 fn build_support_maps(bricks: &[Brick]) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
     // TODO: use iterator::unzip
     let support_map = bricks
@@ -212,7 +270,7 @@ fn build_support_maps(bricks: &[Brick]) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
         .map(|b| {
             let mut supporting = vec![];
             for (i, b2) in bricks.iter().enumerate() {
-                if b.supports(&b2) {
+                if b.supports(b2) {
                     supporting.push(i);
                 }
             }
@@ -226,7 +284,7 @@ fn build_support_maps(bricks: &[Brick]) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
         .map(|b| {
             let mut supported_by = vec![];
             for (i, b2) in bricks.iter().enumerate() {
-                if b2.supports(&b) {
+                if b2.supports(b) {
                     supported_by.push(i);
                 }
             }
@@ -236,16 +294,34 @@ fn build_support_maps(bricks: &[Brick]) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
     (support_map, supported_by_map)
 }
 
-pub fn part1() -> usize {
-    let bricks = parse(INPUT);
-    let bricks = simulate_bricks(bricks);
+// The bricks after they have all settled, with what each one supports and
+// is supported by
+pub struct Settled {
+    pub bricks: Vec<Brick>,
+    pub support_map: Vec<Vec<usize>>,
+    pub supported_by_map: Vec<Vec<usize>>,
+}
 
+pub fn parse(input: &str) -> Result<Settled, ParseError> {
+    let bricks = simulate_bricks(parse_bricks(input)?);
     let (support_map, supported_by_map) = build_support_maps(&bricks);
+    Ok(Settled {
+        bricks,
+        support_map,
+        supported_by_map,
+    })
+}
+
+pub fn part1(settled: &Settled) -> usize {
+    let Settled {
+        support_map,
+        supported_by_map,
+        ..
+    } = settled;
 
     let mut count = 0;
 
-    for i in 0..bricks.len() {
-        let supporting = &support_map[i];
+    for supporting in support_map {
         if supporting
             .iter()
             .all(|&sup| supported_by_map[sup].len() > 1)
@@ -257,11 +333,12 @@ pub fn part1() -> usize {
     count
 }
 
-pub fn part2() -> usize {
-    let bricks = parse(INPUT);
-    let bricks = simulate_bricks(bricks);
-
-    let (support_map, supported_by_map) = build_support_maps(&bricks);
+pub fn part2(settled: &Settled) -> usize {
+    let Settled {
+        bricks,
+        support_map,
+        supported_by_map,
+    } = settled;
 
     let mut total_fall = 0;
     for i in 0..bricks.len() {
@@ -271,19 +348,84 @@ pub fn part2() -> usize {
         let mut visit = support_map[i].clone();
         while let Some(b) = visit.pop() {
             // there are no bricks suporting this one that aren't destroyed or fallen
-            if supported_by_map[b]
+            if !supported_by_map[b]
                 .iter()
-                .filter(|&supporting_b| !fallen.contains(supporting_b))
-                .next()
-                .is_none()
+                .any(|supporting_b| !fallen.contains(supporting_b))
             {
                 fallen.insert(b);
                 visit.extend(&support_map[b]);
             }
         }
-        // println!("remove {i}, {fallen:?} fall");
+        crate::debug!("remove {i}, {fallen:?} fall");
 
         total_fall += (fallen.len() - 1);
     }
     total_fall
 }
+
+// Bricks of up to four cubes in a ten by ten column, in mid-air and not
+// overlapping
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let count = 1200 * scale;
+    let height = (count as u32 / 12).max(10);
+    let mut filled = FxHashSet::default();
+    let mut bricks = Vec::with_capacity(count);
+    while bricks.len() < count {
+        let len = rng.gen_range(0..4);
+        let start = Pos {
+            x: rng.gen_range(0..10),
+            y: rng.gen_range(0..10),
+            z: NonZeroU32::new(rng.gen_range(1..=height)).unwrap(),
+        };
+        let mut end = start;
+        match rng.gen_range(0..3) {
+            0 => end.x += len,
+            1 => end.y += len,
+            _ => end.z = end.z.saturating_add(len),
+        }
+        let brick = Brick(start, end);
+        if end.x > 9 || end.y > 9 || brick.points().any(|p| filled.contains(&p)) {
+            continue;
+        }
+        filled.extend(brick.points());
+        bricks.push(brick);
+    }
+    let lines: Vec<String> = bricks
+        .iter()
+        .map(|Brick(a, b)| format!("{},{},{}~{},{},{}", a.x, a.y, a.z, b.x, b.y, b.z))
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day22>(EXAMPLE, 1), "5");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day22>(EXAMPLE, 2), "7");
+    }
+
+    #[test]
+    fn diagonal_brick() {
+        assert_eq!(
+            parse_error::<Day22>("1,0,1~1,2,1\n0,0,2~2,2,2"),
+            "day 22, line 2, column 1: expected a brick one cube wide, found '0'"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, Alternative, ParseError, Solution};
+use crate::visualize::{self, Render, Rgb};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "A Long Walk";
+    const IMPL: &'static str = "brute-force";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        check_grid(input, input, "'#', '.' or a slope", |b| {
+            b"#.><^v".contains(&b)
+        })?;
+        if !input.contains('.') {
+            return Err(ParseError::at_offset(input, input.len(), "a path to walk"));
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+
+    fn alternatives() -> Vec<Alternative<Self>> {
+        vec![Alternative {
+            name: "compressed",
+            part1: |input| compressed::part1(input).into(),
+            part2: |input| compressed::part2(input).into(),
+        }]
+    }
+}
+
+#[derive(Clone)]
+struct BitSet {
+    inner: Vec<bool>,
+    last: Option<usize>,
+    len: usize,
+}
+
+impl BitSet {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Vec::with_capacity(capacity),
+            last: None,
+            len: 0,
+        }
+    }
+
+    pub fn set(&mut self, idx: usize) {
+        if idx >= self.inner.len() {
+            self.inner.resize(idx + 1, false);
+        }
+        if !self.inner[idx] {
+            self.len += 1;
+            self.inner[idx] = true;
+        }
+        self.last = Some(idx);
+    }
+
+    pub fn get(&self, idx: usize) -> bool {
+        self.inner.get(idx).copied().unwrap_or(false)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+fn explore<const FOLLOW_SLOPES: bool>(
+    graph: &[u8],
+    width: usize,
+    start: usize,
+    end: usize,
+) -> usize {
+    let mut longest = 0;
+    let mut path = BitSet::with_capacity(graph.len());
+    path.set(start);
+    // queue of ongoing paths
+    let mut queue = vec![path];
+    let width = width as isize;
+
+    'queue: while let Some(mut path) = queue.pop() {
+        if path.len() > 7000 {
+            continue;
+        }
+        let node = path.last.unwrap();
+        for (d_n, &direction) in [-width, 1, width, -1].iter().enumerate() {
+            if FOLLOW_SLOPES {
+                match (graph[node], d_n) {
+                    (b'.', _) | (b'^', 0) | (b'>', 1) | (b'v', 2) | (b'<', 3) => (),
+                    _ => continue,
+                }
+            }
+
+            let Some(np) = node.checked_add_signed(direction) else {
+                continue;
+            };
+
+            // no backtracking
+            if path.get(np) {
+                continue;
+            }
+            // reached end, don't explore this path further
+            if np == end && path.len() > longest {
+                longest = path.len();
+                crate::info!("new longest: {longest}");
+                crate::frame!(
+                    if FOLLOW_SLOPES {
+                        "longest-part1"
+                    } else {
+                        "longest-part2"
+                    },
+                    Walk {
+                        graph,
+                        width: width as usize,
+                        path: &path
+                    }
+                );
+                continue 'queue;
+            }
+
+            if matches!(graph.get(np), Some(b'.' | b'>' | b'<' | b'v' | b'^')) {
+                let mut new_path = path.clone();
+                new_path.set(np);
+                queue.push(new_path)
+            }
+        }
+    }
+    longest
+}
+
+// The trails with a walk along them
+struct Walk<'a> {
+    graph: &'a [u8],
+    width: usize,
+    path: &'a BitSet,
+}
+
+impl Render for Walk<'_> {
+    fn size(&self) -> (usize, usize) {
+        (self.width - 1, self.graph.len().div_ceil(self.width))
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Rgb {
+        let i = y * self.width + x;
+        if self.path.get(i) {
+            visualize::RED
+        } else if self.graph[i] == b'#' {
+            visualize::GREY
+        } else {
+            visualize::BLACK
+        }
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    let width = input
+        .bytes()
+        .position(|b| b == b'\n')
+        .unwrap_or(input.len())
+        + 1;
+    let start = input.bytes().position(|b| b == b'.').unwrap();
+    let end = input.bytes().rposition(|b| b == b'.').unwrap();
+    explore::<true>(input.as_bytes(), width, start, end)
+}
+
+pub fn part2(input: &str) -> usize {
+    let width = input
+        .bytes()
+        .position(|b| b == b'\n')
+        .unwrap_or(input.len())
+        + 1;
+    let start = input.bytes().position(|b| b == b'.').unwrap();
+    let end = input.bytes().rposition(|b| b == b'.').unwrap();
+    explore::<false>(input.as_bytes(), width, start, end)
+}
+
+// Searches a graph of just the junctions instead, with each corridor between
+// two of them boiled down to its length
+pub mod compressed {
+    use rustc_hash::FxHashMap;
+
+    // Cells that can be stepped onto from `at`, only going downhill on slopes
+    // if `slopes`
+    fn moves(
+        graph: &[u8],
+        width: usize,
+        at: usize,
+        slopes: bool,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let w = width as isize;
+        [(-w, b'^'), (1, b'>'), (w, b'v'), (-1, b'<')]
+            .into_iter()
+            .filter_map(move |(diff, slope)| {
+                if slopes && graph[at] != b'.' && graph[at] != slope {
+                    return None;
+                }
+                let next = at.checked_add_signed(diff)?;
+                matches!(graph.get(next), Some(b'.' | b'>' | b'<' | b'v' | b'^')).then_some(next)
+            })
+    }
+
+    // The longest walk from `from` to `to` that doesn't go through anywhere in
+    // `seen`
+    fn search(
+        edges: &[Vec<(usize, usize)>],
+        from: usize,
+        to: usize,
+        seen: &mut [bool],
+    ) -> Option<usize> {
+        if from == to {
+            return Some(0);
+        }
+        seen[from] = true;
+        let mut longest = None;
+        for &(next, len) in &edges[from] {
+            if !seen[next] {
+                let rest = search(edges, next, to, seen);
+                longest = longest.max(rest.map(|rest| len + rest));
+            }
+        }
+        seen[from] = false;
+        longest
+    }
+
+    fn longest(input: &str, slopes: bool) -> usize {
+        let graph = input.as_bytes();
+        let width = input
+            .bytes()
+            .position(|b| b == b'\n')
+            .unwrap_or(input.len())
+            + 1;
+        let start = input.bytes().position(|b| b == b'.').unwrap();
+        let end = input.bytes().rposition(|b| b == b'.').unwrap();
+        // both ends, then everywhere three or more paths meet
+        let mut nodes = vec![start, end];
+        nodes.extend((0..graph.len()).filter(|&i| {
+            graph[i] != b'#' && graph[i] != b'\n' && moves(graph, width, i, false).count() > 2
+        }));
+        let index: FxHashMap<_, _> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        // follow each corridor leaving each node until it reaches another
+        let mut edges: Vec<Vec<(usize, usize)>> = nodes
+            .iter()
+            .map(|&from| {
+                moves(graph, width, from, slopes)
+                    .filter_map(|first| {
+                        let (mut prev, mut at, mut len) = (from, first, 1);
+                        loop {
+                            if let Some(&to) = index.get(&at) {
+                                return Some((to, len));
+                            }
+                            let next = moves(graph, width, at, slopes).find(|&n| n != prev)?;
+                            (prev, at, len) = (at, next, len + 1);
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        // leaving the last junction before the end any other way would cut
+        // the end off
+        let before_end: Vec<_> = (0..nodes.len())
+            .filter(|&n| edges[n].iter().any(|&(to, _)| to == 1))
+            .collect();
+        if let [last] = before_end[..] {
+            edges[last].retain(|&(to, _)| to == 1);
+        }
+        search(&edges, 0, 1, &mut vec![false; nodes.len()]).unwrap_or(0)
+    }
+
+    pub fn part1(input: &str) -> usize {
+        longest(input, true)
+    }
+
+    pub fn part2(input: &str) -> usize {
+        longest(input, false)
+    }
+}
+
+// A six by six lattice of junctions joined by corridors that have slopes at
+// both ends, all leading right or down like the real maze. A few corridors
+// are left out, but never along the top or down the right, so the end can
+// always be reached.
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    const JUNCTIONS: usize = 6;
+    let spacing = generate::side(20, scale);
+    let size = spacing * (JUNCTIONS + 1) + 1;
+    let at = |i: usize| spacing * (i + 1);
+    let mut rows = vec![vec![b'#'; size]; size];
+    // from the start to the first junction, and the last junction to the end
+    for row in &mut rows[..at(0)] {
+        row[1] = b'.';
+    }
+    rows[at(0)][1..at(0)].fill(b'.');
+    let last = at(JUNCTIONS - 1);
+    rows[last][last + 1..size - 1].fill(b'.');
+    for row in &mut rows[last..] {
+        row[size - 2] = b'.';
+    }
+    for j in 0..JUNCTIONS {
+        for i in 0..JUNCTIONS {
+            let (x, y) = (at(i), at(j));
+            rows[y][x] = b'.';
+            if i + 1 < JUNCTIONS && (j == 0 || rng.gen_bool(0.85)) {
+                rows[y][x + 1..at(i + 1)].fill(b'.');
+                rows[y][x + 1] = b'>';
+                rows[y][at(i + 1) - 1] = b'>';
+            }
+            if j + 1 < JUNCTIONS && (i + 1 == JUNCTIONS || rng.gen_bool(0.85)) {
+                for row in &mut rows[y + 1..at(j + 1)] {
+                    row[x] = b'.';
+                }
+                rows[y + 1][x] = b'v';
+                rows[at(j + 1) - 1][x] = b'v';
+            }
+        }
+    }
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day23>(EXAMPLE, 1), "94");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day23>(EXAMPLE, 2), "154");
+    }
+
+    #[test]
+    fn compressed_example() {
+        assert_eq!(compressed::part1(EXAMPLE), 94);
+        assert_eq!(compressed::part2(EXAMPLE), 154);
+    }
+}
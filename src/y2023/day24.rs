@@ -0,0 +1,409 @@
+use std::ops::{Mul, RangeInclusive};
+
+use crate::answer::Answer;
+#[cfg(feature = "z3")]
+use crate::solution::Alternative;
+use crate::solution::{parse_number, ParseError, Solution};
+#[cfg(not(feature = "z3"))]
+use native::{count_crossings, throw_rock};
+use rand::rngs::StdRng;
+use rand::Rng;
+use rustc_hash::FxHashSet;
+#[cfg(feature = "z3")]
+use with_z3::{count_crossings, throw_rock};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Never Tell Me The Odds";
+    const IMPL: &'static str = if cfg!(feature = "z3") { "z3" } else { "native" };
+    type Parsed<'a> = Vec<Hailstone>;
+
+    fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(hailstones: &Vec<Hailstone>) -> Answer {
+        part1(hailstones).into()
+    }
+
+    fn part2(hailstones: &Vec<Hailstone>) -> Answer {
+        part2(hailstones).into()
+    }
+
+    // the native solvers are always there to check z3 against
+    #[cfg(feature = "z3")]
+    fn alternatives() -> Vec<Alternative<Self>> {
+        vec![Alternative {
+            name: "native",
+            part1: |hailstones| native::count_crossings(hailstones, TEST_RANGE).into(),
+            part2: |hailstones| native::throw_rock(hailstones).into(),
+        }]
+    }
+}
+
+const TEST_RANGE: RangeInclusive<i64> = 200000000000000..=400000000000000;
+
+// pos + t * D = pos2 + s * D2
+// t = ((pos2 - pos) x D2) / (D x D2)
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Vector3 {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl std::ops::Mul<i64> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vector3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl std::ops::Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+// Position and velocity
+pub type Hailstone = (Vector3, Vector3);
+
+// Three numbers like `19, 13, 30`, lined up with any number of spaces
+fn parse_vector(input: &str, s: &str) -> Result<Vector3, ParseError> {
+    let coords: Vec<&str> = s.split(',').map(str::trim).collect();
+    if coords.len() < 3 {
+        return Err(ParseError::at(
+            input,
+            &s[s.len()..],
+            "',' and another number",
+        ));
+    }
+    if coords.len() > 3 {
+        let (third, _) = s.match_indices(',').nth(2).unwrap();
+        return Err(ParseError::at(input, &s[third..], "' @ ' or end of line"));
+    }
+    Ok(Vector3 {
+        x: parse_number(input, coords[0])?,
+        y: parse_number(input, coords[1])?,
+        z: parse_number(input, coords[2])?,
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    input
+        .lines()
+        .map(|l| {
+            let Some((p, v)) = l.split_once(" @ ") else {
+                return Err(ParseError::at(input, &l[l.len()..], "' @ '"));
+            };
+            Ok((parse_vector(input, p)?, parse_vector(input, v)?))
+        })
+        .collect()
+}
+
+pub fn part1(hailstones: &[Hailstone]) -> usize {
+    count_crossings(hailstones, TEST_RANGE)
+}
+
+pub fn part2(hailstones: &[Hailstone]) -> i64 {
+    throw_rock(hailstones)
+}
+
+// Exact integer versions of the z3 solvers, for builds without libz3
+pub mod native {
+    use super::*;
+
+    fn cross(a: (i128, i128), b: (i128, i128)) -> i128 {
+        a.0 * b.1 - a.1 * b.0
+    }
+
+    // Where the paths of two hailstones moving in the x/y plane cross, as
+    // (time for `a`, time for `b`, common denominator). The denominator is
+    // positive, and zero if the paths are parallel.
+    fn crossing(
+        a: (i128, i128),
+        va: (i128, i128),
+        b: (i128, i128),
+        vb: (i128, i128),
+    ) -> (i128, i128, i128) {
+        let det = cross(va, vb);
+        let diff = (b.0 - a.0, b.1 - a.1);
+        let (ta, tb) = (cross(diff, vb), cross(diff, va));
+        if det < 0 {
+            (-ta, -tb, -det)
+        } else {
+            (ta, tb, det)
+        }
+    }
+
+    fn xy(v: &Vector3) -> (i128, i128) {
+        (v.x as i128, v.y as i128)
+    }
+
+    pub fn count_crossings(hailstones: &[Hailstone], range: RangeInclusive<i64>) -> usize {
+        let (lo, hi) = (*range.start() as i128, *range.end() as i128);
+        let mut collide = 0;
+        for (i, (pos_a, vel_a)) in hailstones.iter().enumerate() {
+            for (pos_b, vel_b) in hailstones.iter().skip(i + 1) {
+                let (a, va) = (xy(pos_a), xy(vel_a));
+                let (ta, tb, det) = crossing(a, va, xy(pos_b), xy(vel_b));
+                if det == 0 || ta < 0 || tb < 0 {
+                    continue;
+                }
+                // compare against the range scaled up by the denominator
+                // rather than dividing
+                let (x, y) = (a.0 * det + ta * va.0, a.1 * det + ta * va.1);
+                let inside = |n| lo * det <= n && n <= hi * det;
+                if inside(x) && inside(y) {
+                    collide += 1;
+                }
+            }
+        }
+        collide
+    }
+
+    // The rock's velocity is small, so try each x/y velocity in turn. Seen
+    // from the rock, every hailstone then passes through the point the rock
+    // is thrown from.
+    const MAX_SPEED: i128 = 1000;
+
+    pub fn throw_rock(hailstones: &[Hailstone]) -> i64 {
+        let [(pos_a, vel_a), rest @ ..] = hailstones else {
+            panic!("no hailstones");
+        };
+        let a = xy(pos_a);
+        for vx in -MAX_SPEED..=MAX_SPEED {
+            'velocity: for vy in -MAX_SPEED..=MAX_SPEED {
+                let relative = |v: &Vector3| (v.x as i128 - vx, v.y as i128 - vy);
+                let va = relative(vel_a);
+                // find another hailstone whose path crosses the first one's
+                let Some((pos_b, vel_b, (ta, tb, det))) = rest.iter().find_map(|(pos_b, vel_b)| {
+                    let crossing = crossing(a, va, xy(pos_b), relative(vel_b));
+                    (crossing.2 != 0).then_some((pos_b, vel_b, crossing))
+                }) else {
+                    continue;
+                };
+                if ta % det != 0 || tb % det != 0 || ta < 0 || tb < 0 {
+                    continue;
+                }
+                let (ta, tb) = (ta / det, tb / det);
+                let start = (a.0 + ta * va.0, a.1 + ta * va.1);
+                if ta == tb {
+                    // both hit at once, so the z velocity can't be worked out
+                    continue;
+                }
+                // z positions of both hailstones when the rock hits them
+                let hit_a = pos_a.z as i128 + ta * vel_a.z as i128;
+                let hit_b = pos_b.z as i128 + tb * vel_b.z as i128;
+                if (hit_a - hit_b) % (ta - tb) != 0 {
+                    continue;
+                }
+                let vz = (hit_a - hit_b) / (ta - tb);
+                let start_z = hit_a - ta * vz;
+                // check the rock hits every other hailstone too
+                for (pos, vel) in rest {
+                    let v = relative(vel);
+                    let p = xy(pos);
+                    let d = (start.0 - p.0, start.1 - p.1);
+                    if cross(d, v) != 0 {
+                        continue 'velocity;
+                    }
+                    let t = if v.0 != 0 {
+                        (d.0 % v.0 == 0).then_some(d.0 / v.0)
+                    } else if v.1 != 0 {
+                        (d.1 % v.1 == 0).then_some(d.1 / v.1)
+                    } else {
+                        // moving alongside the rock, so it has to start there
+                        (d == (0, 0)).then_some(0)
+                    };
+                    let Some(t) = t else {
+                        continue 'velocity;
+                    };
+                    if t < 0 || start_z + t * vz != pos.z as i128 + t * vel.z as i128 {
+                        continue 'velocity;
+                    }
+                }
+                return (start.0 + start.1 + start_z) as i64;
+            }
+        }
+        panic!("no rock velocity up to {MAX_SPEED} hits every hailstone")
+    }
+}
+
+#[cfg(feature = "z3")]
+pub mod with_z3 {
+    use super::*;
+    use z3::{
+        ast::{Ast, Int, Real},
+        SatResult, Solver,
+    };
+
+    // Pairs of hailstones whose paths cross inside `range` on both the x and y axis
+    pub fn count_crossings(hailstones: &[Hailstone], range: RangeInclusive<i64>) -> usize {
+        let mut collide = 0;
+        let cfg = z3::Config::new();
+        let ctx = z3::Context::new(&cfg);
+        for (i, (pos_a, vel_a)) in hailstones.iter().enumerate() {
+            crate::debug!("checking {i}");
+            for (pos_b, vel_b) in hailstones.iter().skip(i + 1) {
+                let solver = z3::Solver::new(&ctx);
+                let pos_a_x = Int::from_i64(&ctx, pos_a.x);
+                let pos_a_y = Int::from_i64(&ctx, pos_a.y);
+                let vel_a_x = Int::from_i64(&ctx, vel_a.x);
+                let vel_a_y = Int::from_i64(&ctx, vel_a.y);
+
+                let pos_b_x = Int::from_i64(&ctx, pos_b.x);
+                let pos_b_y = Int::from_i64(&ctx, pos_b.y);
+                let vel_b_x = Int::from_i64(&ctx, vel_b.x);
+                let vel_b_y = Int::from_i64(&ctx, vel_b.y);
+
+                let time_a = Real::fresh_const(&ctx, "time_a");
+                let time_b = Real::fresh_const(&ctx, "time_b");
+                let line_a_x = pos_a_x.to_real() + vel_a_x.to_real() * &time_a;
+                let line_a_y = pos_a_y.to_real() + vel_a_y.to_real() * &time_a;
+
+                let line_b_x = pos_b_x.to_real() + vel_b_x.to_real() * &time_b;
+                let line_b_y = pos_b_y.to_real() + vel_b_y.to_real() * &time_b;
+
+                solver.assert(&time_a.ge(&Real::from_real(&ctx, 0, 1)));
+                solver.assert(&time_b.ge(&Real::from_real(&ctx, 0, 1)));
+                solver.assert(&line_a_x._eq(&line_b_x));
+                solver.assert(&line_a_y._eq(&line_b_y));
+                solver.assert(&line_a_x.ge(&Real::from_int(&Int::from_i64(&ctx, *range.start()))));
+                solver.assert(&line_a_x.le(&Real::from_int(&Int::from_i64(&ctx, *range.end()))));
+                solver.assert(&line_a_y.ge(&Real::from_int(&Int::from_i64(&ctx, *range.start()))));
+                solver.assert(&line_a_y.le(&Real::from_int(&Int::from_i64(&ctx, *range.end()))));
+                if let z3::SatResult::Sat = solver.check() {
+                    collide += 1;
+                }
+            }
+        }
+        collide
+    }
+
+    // The sum of the coordinates of the position to throw a rock from
+    pub fn throw_rock(hailstones: &[Hailstone]) -> i64 {
+        let mut collide = 0;
+        let cfg = z3::Config::new();
+        let ctx = z3::Context::new(&cfg);
+        let s = Solver::new(&ctx);
+
+        let p_x = Real::new_const(&ctx, "p_x");
+        let p_y = Real::new_const(&ctx, "p_y");
+        let p_z = Real::new_const(&ctx, "p_z");
+        let v_x = Real::new_const(&ctx, "v_x");
+        let v_y = Real::new_const(&ctx, "v_y");
+        let v_z = Real::new_const(&ctx, "v_z");
+        // apparently solving for 3 is fine
+        for (i, (pos, vel)) in hailstones.iter().enumerate().take(3) {
+            let time = Real::fresh_const(&ctx, "time");
+            let thrown_x = &p_x + &v_x * &time;
+            let thrown_y = &p_y + &v_y * &time;
+            let thrown_z = &p_z + &v_z * &time;
+
+            let this_x =
+                Int::from_i64(&ctx, pos.x).to_real() + Int::from_i64(&ctx, vel.x).to_real() * &time;
+            let this_y =
+                Int::from_i64(&ctx, pos.y).to_real() + Int::from_i64(&ctx, vel.y).to_real() * &time;
+            let this_z =
+                Int::from_i64(&ctx, pos.z).to_real() + Int::from_i64(&ctx, vel.z).to_real() * &time;
+
+            s.assert(&thrown_x._eq(&this_x));
+            s.assert(&thrown_y._eq(&this_y));
+            s.assert(&thrown_z._eq(&this_z));
+        }
+        if let SatResult::Sat = s.check() {
+            let m = s.get_model().unwrap();
+            [p_x, p_y, p_z]
+                .iter()
+                .map(|p| {
+                    let Some((n, 1)) = m.get_const_interp(p).and_then(|v| v.as_real()) else {
+                        unreachable!();
+                    };
+                    n
+                })
+                .sum()
+        } else {
+            panic!("unsat")
+        }
+    }
+}
+
+// Hailstones that all get hit by a rock thrown from a random place, each at
+// a different time
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let rock_pos = [(); 3].map(|_| rng.gen_range(100_000_000_000_000..400_000_000_000_000i64));
+    let rock_vel = [(); 3].map(|_| rng.gen_range(-300..=300i64));
+    let mut times = FxHashSet::default();
+    let lines: Vec<String> = (0..300 * scale)
+        .map(|_| {
+            let t = loop {
+                let t = rng.gen_range(10_000_000_000..1_000_000_000_000i64);
+                if times.insert(t) {
+                    break t;
+                }
+            };
+            // never moving alongside the rock in x or y
+            let vel = loop {
+                let vel = [(); 3].map(|_| rng.gen_range(-500..=500i64));
+                if vel[0] != rock_vel[0] && vel[1] != rock_vel[1] {
+                    break vel;
+                }
+            };
+            let pos: Vec<i64> = (0..3)
+                .map(|k| rock_pos[k] + (rock_vel[k] - vel[k]) * t)
+                .collect();
+            format!(
+                "{}, {}, {} @ {}, {}, {}",
+                pos[0], pos[1], pos[2], vel[0], vel[1], vel[2]
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn part1_example() {
+        let hailstones = parse(EXAMPLE).unwrap();
+        assert_eq!(count_crossings(&hailstones, 7..=27), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day24>(EXAMPLE, 2), "47");
+    }
+
+    #[test]
+    fn missing_coordinate() {
+        assert_eq!(
+            parse_error::<Day24>("19, 13, 30 @ -2, 1, -2\n18, 19 @ -1, -1, -2"),
+            "day 24, line 2, column 7: expected ',' and another number, found ' '"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt::Debug;
+
+use crate::answer::Answer;
+use crate::solution::{Alternative, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Snowverload";
+    const IMPL: &'static str = "karger";
+    type Parsed<'a> = Graph;
+
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        parse(input)
+    }
+
+    fn part1(graph: &Graph) -> Answer {
+        part1(graph).into()
+    }
+
+    fn part2(_graph: &Graph) -> Answer {
+        // there's no puzzle for the last part 2
+        Answer::Nothing
+    }
+
+    fn alternatives() -> Vec<Alternative<Self>> {
+        vec![Alternative {
+            name: "stoer-wagner",
+            part1: |graph| stoer_wagner::part1(graph).into(),
+            part2: Self::part2,
+        }]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeName(u32);
+
+impl Debug for NodeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, r#""{self}""#)
+    }
+}
+
+impl std::fmt::Display for NodeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(std::str::from_utf8(&self.0.to_le_bytes()[1..]).unwrap())
+    }
+}
+
+impl NodeName {
+    /// `s`, which must be a slice of `input`, as a name
+    pub fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
+        match *s.as_bytes() {
+            [a, b, c] if s.bytes().all(|b| b.is_ascii_lowercase()) => {
+                Ok(Self(u32::from_le_bytes([0, a, b, c])))
+            }
+            _ => Err(ParseError::at(
+                input,
+                s,
+                "a name of three lowercase letters",
+            )),
+        }
+    }
+}
+
+fn get_rand_two(max: usize) -> (usize, usize) {
+    let v1 = rand::random::<usize>() % max;
+    let v2 = loop {
+        let val = rand::random::<usize>() % max;
+        if val != v1 {
+            break val;
+        }
+    };
+    (v1, v2)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Edge(pub NodeName, pub NodeName);
+
+impl Edge {
+    pub fn normalize(self) -> Self {
+        let Self(from, to) = self;
+        let mut edge = [from, to];
+        edge.sort_unstable();
+        Self(edge[0], edge[1])
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Graph {
+    // The usize counts the total number of vertices this 'supervertex' represents
+    pub vertices: FxHashMap<NodeName, usize>,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn from_input(input: &str) -> Result<Self, ParseError> {
+        let mut vertices = FxHashMap::default();
+        let mut edges = Vec::new();
+        for l in input.lines() {
+            let Some((from, to)) = l.split_once(": ") else {
+                return Err(ParseError::at(input, &l[l.len()..], "': '"));
+            };
+            let from = NodeName::parse(input, from)?;
+            vertices.insert(from, 1);
+            for to in to.split(' ') {
+                let to = NodeName::parse(input, to)?;
+                vertices.insert(to, 1);
+                edges.push(Edge(from, to).normalize());
+            }
+        }
+        Ok(Self { vertices, edges })
+    }
+
+    /// Combine 'v2' into the node 'v1'
+    /// This removes v2, and any edges to it will now link to v1
+    pub fn contract(&mut self, v1: NodeName, v2: NodeName) {
+        self.edges.remove(
+            self.edges
+                .iter()
+                .position(|&Edge(e1, e2)| e1 == v1 && e2 == v2)
+                .unwrap(),
+        );
+        let Some(count) = self.vertices.remove(&v2) else {
+            unreachable!()
+        };
+        *self.vertices.get_mut(&v1).unwrap() += count;
+        for i in (0..self.edges.len()).rev() {
+            let edge = &mut self.edges[i];
+            if edge.0 == v2 {
+                edge.0 = v1;
+            } else if edge.1 == v2 {
+                edge.1 = v1;
+            }
+            if edge.0 == edge.1 {
+                self.edges.remove(i);
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Graph, ParseError> {
+    Graph::from_input(input)
+}
+
+pub fn part1(graph: &Graph) -> usize {
+    let mut rng = rand::thread_rng();
+    // Karger's algorithm: randomly combine vertices until we have 2
+    // If we have 3 edges remaining (all equal), then we have successfully combined each graph 'half'
+    // Multiply the number of merged vertices
+    let squeezed = 'outer: loop {
+        let mut current_graph = graph.clone();
+        while current_graph.vertices.len() > 2 {
+            let &Edge(from, to) = current_graph.edges.choose(&mut rng).unwrap();
+            current_graph.contract(from, to);
+        }
+        if current_graph.edges.len() == 3 {
+            break 'outer current_graph;
+        }
+    };
+    squeezed.vertices.values().product()
+}
+
+// The Stoer-Wagner minimum cut, which finds the three wires the same way
+// every time rather than by luck
+pub mod stoer_wagner {
+    use super::{Edge, Graph};
+    use rustc_hash::FxHashMap;
+    use std::collections::BinaryHeap;
+
+    pub fn part1(graph: &Graph) -> usize {
+        let mut names: Vec<_> = graph.vertices.keys().copied().collect();
+        names.sort_unstable();
+        let index: FxHashMap<_, _> = names.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let n = names.len();
+        // wires between each pair of vertices, which add up as they're merged
+        let mut adjacent = vec![FxHashMap::<usize, u32>::default(); n];
+        for &Edge(a, b) in &graph.edges {
+            let (a, b) = (index[&a], index[&b]);
+            *adjacent[a].entry(b).or_default() += 1;
+            *adjacent[b].entry(a).or_default() += 1;
+        }
+        let mut size: Vec<usize> = names.iter().map(|name| graph.vertices[name]).collect();
+        let total: usize = size.iter().sum();
+        let mut merged = vec![false; n];
+        // the fewest wires cut so far, and how many components were on one side
+        let mut best = (u32::MAX, 0);
+        for _ in 1..n {
+            // add whichever vertex is most tightly connected to those added so
+            // far, until they're all in
+            let start = (0..n).find(|&v| !merged[v]).unwrap();
+            let mut connection = vec![0; n];
+            let mut added = vec![false; n];
+            let mut heap = BinaryHeap::from([(0, start)]);
+            let (mut prev, mut last, mut cut) = (start, start, 0);
+            while let Some((c, v)) = heap.pop() {
+                if added[v] || c != connection[v] {
+                    continue;
+                }
+                added[v] = true;
+                (prev, last, cut) = (last, v, c);
+                for (&u, &wires) in &adjacent[v] {
+                    if !added[u] {
+                        connection[u] += wires;
+                        heap.push((connection[u], u));
+                    }
+                }
+            }
+            // the last one added against everything else is a cut
+            if cut < best.0 {
+                best = (cut, size[last]);
+            }
+            // the puzzle promises three wires, so there's nothing smaller
+            if best.0 == 3 {
+                break;
+            }
+            merged[last] = true;
+            size[prev] += size[last];
+            for (u, wires) in std::mem::take(&mut adjacent[last]) {
+                adjacent[u].remove(&last);
+                if u != prev {
+                    *adjacent[prev].entry(u).or_default() += wires;
+                    *adjacent[u].entry(prev).or_default() += wires;
+                }
+            }
+        }
+        best.1 * (total - best.1)
+    }
+}
+
+// Two random clusters where every component is wired to at least four
+// others, joined by exactly three wires
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    // names are three letters, so there's a limit to how big this can get
+    let count = (1500 * scale).min(26 * 26 * 26);
+    let mut names: Vec<String> = (0..26 * 26 * 26)
+        .map(|n: u32| {
+            [n / 676, n / 26 % 26, n % 26]
+                .map(|c| (b'a' + c as u8) as char)
+                .iter()
+                .collect()
+        })
+        .collect();
+    names.shuffle(rng);
+    names.truncate(count);
+    let split = rng.gen_range(count / 3..=2 * count / 3);
+
+    let mut edges = FxHashSet::default();
+    for (lo, hi) in [(0, split), (split, count)] {
+        for a in lo..hi {
+            let mut added = 0;
+            while added < 4 {
+                let b = rng.gen_range(lo..hi);
+                if a != b && edges.insert((a.min(b), a.max(b))) {
+                    added += 1;
+                }
+            }
+        }
+    }
+    let mut cut = 0;
+    while cut < 3 {
+        if edges.insert((rng.gen_range(0..split), rng.gen_range(split..count))) {
+            cut += 1;
+        }
+    }
+
+    // list each wire once, on the line of one of its ends
+    let mut wires: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    edges.sort_unstable();
+    for (a, b) in edges {
+        let (from, to) = if rng.gen() { (a, b) } else { (b, a) };
+        wires.entry(from).or_default().push(to);
+    }
+    let mut lines: Vec<String> = wires
+        .iter()
+        .map(|(from, to)| {
+            let to: Vec<&str> = to.iter().map(|&n| names[n].as_str()).collect();
+            format!("{}: {}", names[*from], to.join(" "))
+        })
+        .collect();
+    lines.sort_unstable();
+    lines.shuffle(rng);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day25>(EXAMPLE, 1), "54");
+    }
+
+    #[test]
+    fn stoer_wagner_example() {
+        assert_eq!(
+            stoer_wagner::part1(&Graph::from_input(EXAMPLE).unwrap()),
+            54
+        );
+    }
+}
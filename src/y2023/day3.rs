@@ -1,10 +1,37 @@
+use crate::answer::Answer;
+use crate::generate;
+use crate::solution::{check_grid, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use rustc_hash::FxHashMap;
 
-const INPUT: &str = include_str!("inputs/3.txt");
+pub struct Day3;
 
-pub fn part1() -> usize {
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        check_grid(input, input, "a digit, '.' or a symbol", |b| {
+            b.is_ascii_graphic()
+        })?;
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+pub fn part1(input: &str) -> usize {
     let mut sum = 0;
-    let mut walker = GridWalker::new(INPUT);
+    let mut walker = GridWalker::new(input);
     while let Some(n) = walker.walk_until_number() {
         let mut n = n as usize;
         let mut adj_symbol = false;
@@ -64,7 +91,7 @@ impl<const N: usize, T> SmallList<N, T> {
     }
 }
 
-pub fn part2() -> usize {
+pub fn part2(input: &str) -> usize {
     let mut gears: FxHashMap<usize, SmallList<2, usize>> =
         FxHashMap::with_capacity_and_hasher(50, Default::default());
 
@@ -76,7 +103,7 @@ pub fn part2() -> usize {
         }
     }
 
-    let mut walker = GridWalker::new(INPUT);
+    let mut walker = GridWalker::new(input);
     while let Some(n) = walker.walk_until_number() {
         let mut adj_star = None;
         let mut n = n as usize;
@@ -121,7 +148,7 @@ impl<'a> GridWalker<'a> {
         Self {
             grid: src.as_bytes(),
             pos: 0,
-            width: src.as_bytes().iter().position(|b| *b == b'\n').unwrap(),
+            width: src.find('\n').unwrap_or(src.len()),
         }
     }
 
@@ -241,3 +268,57 @@ impl<'a> GridWalker<'a> {
         }
     }
 }
+
+// Numbers of up to three digits scattered over a grid along with symbols
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let size = generate::side(140, scale);
+    let mut rows = vec![vec![b'.'; size]; size];
+    for row in rows.iter_mut() {
+        let mut x = 0;
+        while x < size {
+            if rng.gen_bool(0.1) {
+                let len = rng.gen_range(1..=3).min(size - x);
+                row[x] = rng.gen_range(b'1'..=b'9');
+                for cell in &mut row[x + 1..x + len] {
+                    *cell = rng.gen_range(b'0'..=b'9');
+                }
+                // keep numbers on the same row apart
+                x += len + 1;
+            } else {
+                if rng.gen_bool(0.04) {
+                    row[x] = *b"**#+$/=%@&-".choose(rng).unwrap();
+                }
+                x += 1;
+            }
+        }
+    }
+    generate::join_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day3>(EXAMPLE, 1), "4361");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day3>(EXAMPLE, 2), "467835");
+    }
+}
@@ -0,0 +1,224 @@
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(matches: &Vec<usize>) -> Answer {
+        part1(matches).into()
+    }
+
+    fn part2(matches: &Vec<usize>) -> Answer {
+        part2(matches).into()
+    }
+}
+
+fn parse_two_byte_num(hi: u8, lo: u8) -> u8 {
+    // correct as long as hi and lo are ASCII digits
+    // or a space, which `check_card` makes sure of
+    (hi & 0xf) * 10 + (lo & 0xf)
+}
+
+// `matches_for_card` reads cards by byte offset, so every card has to be as
+// long as the first with its ':' in the same place, and each number has to
+// be two characters wide
+fn check_card(input: &str, line: &str, first: &str, colon: usize) -> Result<(), ParseError> {
+    let b = line.as_bytes();
+    let error = |at: usize, expected: &str| {
+        Err(ParseError::at(
+            input,
+            line.get(at..).unwrap_or(&line[line.len()..]),
+            expected,
+        ))
+    };
+    let Some(number) = line.strip_prefix("Card ") else {
+        return error(0, "'Card '");
+    };
+    let digits = number.trim_start_matches(' ');
+    let digits = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    if digits == 0 {
+        return error(
+            line.len() - number.trim_start_matches(' ').len(),
+            "a card number",
+        );
+    }
+    if b.get(colon) != Some(&b':') {
+        return error(colon, "':' lined up with the first card's");
+    }
+    let mut pos = colon + 1;
+    let mut bar = false;
+    while pos < b.len() {
+        if b[pos] != b' ' {
+            return error(pos, "' '");
+        }
+        match b.get(pos + 1..pos + 3) {
+            _ if b.get(pos + 1) == Some(&b'|') && !bar => {
+                bar = true;
+                pos += 2;
+            }
+            Some(&[hi, lo]) if matches!(hi, b' ' | b'1'..=b'9') && lo.is_ascii_digit() => {
+                pos += 3;
+            }
+            _ => return error(pos + 1, "a number below 100"),
+        }
+    }
+    if !bar {
+        return error(line.len(), "' |' between the winning numbers and yours");
+    }
+    if line.len() < first.len() {
+        return error(line.len(), "as many numbers as the first card");
+    }
+    if line.len() > first.len() {
+        return error(first.len(), "end of line");
+    }
+    Ok(())
+}
+
+fn matches_for_card(skip: usize, card: &[u8]) -> usize {
+    let (_, l) = card.split_at(skip);
+    let mut pos = 0;
+    let mut winning_numbers = [false; 100];
+    while let Some(&[hi, lo]) = l.get(pos..pos + 2) {
+        if hi == b'|' {
+            pos += 2;
+            break;
+        }
+        winning_numbers[parse_two_byte_num(hi, lo) as usize] = true;
+        pos += 3;
+    }
+    let mut sum = 0;
+    while let Some(&[hi, lo]) = l.get(pos..pos + 2) {
+        if winning_numbers[parse_two_byte_num(hi, lo) as usize] {
+            sum += 1;
+        }
+        pos += 3;
+    }
+    sum
+}
+
+// Number of winning numbers on each card
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let first = input.lines().next().unwrap_or_default();
+    let Some(colon) = first.find(':') else {
+        return Err(ParseError::at(input, &first[first.len()..], "':'"));
+    };
+    for line in input.lines() {
+        check_card(input, line, first, colon)?;
+    }
+    let skip = colon + 2;
+    // a single card has no newline at all
+    let line_length = input.find('\n').unwrap_or(input.len()) + 1;
+    Ok(input
+        .as_bytes()
+        .chunks(line_length)
+        .map(|l| matches_for_card(skip, l))
+        .collect())
+}
+
+pub fn part1(matches: &[usize]) -> usize {
+    matches
+        .iter()
+        .map(|&m| if m > 0 { 1 << (m - 1) } else { 0 })
+        .sum()
+}
+
+pub fn part2(matches: &[usize]) -> usize {
+    let mut amounts_per_card = vec![1; matches.len()];
+
+    for (i, &matches) in matches.iter().enumerate() {
+        let copies = amounts_per_card[i];
+        // for each match, add a copy of subsequent cards for each copy of this card
+        for j in 0..matches {
+            amounts_per_card[i + j + 1] += copies;
+        }
+    }
+    amounts_per_card.iter().sum()
+}
+
+// Cards with ten winning numbers and 25 numbers each. Most cards match
+// nothing, so the number of copies in part 2 doesn't run away.
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let cards = 200 * scale;
+    let width = cards.to_string().len();
+    let format = |numbers: &[u8]| {
+        let numbers: Vec<String> = numbers.iter().map(|n| format!("{n:>2}")).collect();
+        numbers.join(" ")
+    };
+    let cards: Vec<String> = (0..cards)
+        .map(|i| {
+            let mut numbers: Vec<u8> = (1..100).collect();
+            numbers.shuffle(rng);
+            let (winning, rest) = numbers.split_at(10);
+            let matches = if rng.gen_bool(0.85) {
+                0
+            } else {
+                rng.gen_range(1..=10)
+            };
+            // copies can't go past the last card
+            let matches = matches.min(cards - i - 1);
+            let mut have: Vec<u8> = winning[..matches]
+                .iter()
+                .chain(&rest[..25 - matches])
+                .copied()
+                .collect();
+            have.shuffle(rng);
+            format!(
+                "Card {:>width$}: {} | {}",
+                i + 1,
+                format(winning),
+                format(&have)
+            )
+        })
+        .collect();
+    cards.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day4>(EXAMPLE, 1), "13");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day4>(EXAMPLE, 2), "30");
+    }
+
+    #[test]
+    fn single_card() {
+        assert_eq!(solve::<Day4>(EXAMPLE.lines().next().unwrap(), 1), "8");
+    }
+
+    #[test]
+    fn bad_number() {
+        assert_eq!(
+            parse_error::<Day4>("Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30"),
+            "day 4, line 2, column 12: expected a number below 100, found 'x2'"
+        );
+    }
+}
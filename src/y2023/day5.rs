@@ -1,44 +1,82 @@
+use crate::answer::Answer;
+use crate::solution::{parse_number, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::fmt::{Debug, Formatter};
 
-/*
-const INPUT: &str = r#"seeds: 79 14 55 13
+pub struct Day5;
 
-seed-to-soil map:
-50 98 2
-52 50 48
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+    type Parsed<'a> = &'a str;
 
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        check(input)?;
+        Ok(input)
+    }
 
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
 
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
 
-humidity-to-location map:
-60 56 37
-56 93 4"#;
-*/
-const INPUT: &str = include_str!("inputs/5.txt");
+// Both parts split the input up and unwrap as they go, so make sure it's a
+// `seeds:` line of pairs and then maps of three numbers a line first
+fn check(input: &str) -> Result<(), ParseError> {
+    let mut blocks = input.split("\n\n");
+    let first = blocks.next().unwrap_or_default();
+    let Some(seeds) = first.strip_prefix("seeds: ") else {
+        return Err(ParseError::at(input, first, "'seeds: '"));
+    };
+    let seeds: Vec<&str> = seeds.split(' ').collect();
+    for seed in &seeds {
+        parse_number::<usize>(input, seed)?;
+    }
+    if !seeds.len().is_multiple_of(2) {
+        let end = &first[first.len()..];
+        return Err(ParseError::at(
+            input,
+            end,
+            "a range length after the last seed",
+        ));
+    }
+    for block in blocks {
+        let mut lines = block.lines();
+        let header = lines.next().unwrap_or_default();
+        if !header.ends_with(" map:") {
+            return Err(ParseError::at(
+                input,
+                header,
+                "a line like 'seed-to-soil map:'",
+            ));
+        }
+        for line in lines {
+            let numbers: Vec<&str> = line.split(' ').collect();
+            for n in &numbers {
+                parse_number::<usize>(input, n)?;
+            }
+            if numbers.len() != 3 {
+                let at = numbers.get(3).copied().unwrap_or(&line[line.len()..]);
+                let expected = if numbers.len() < 3 {
+                    "a number"
+                } else {
+                    "end of line"
+                };
+                return Err(ParseError::at(input, at, expected));
+            }
+        }
+    }
+    Ok(())
+}
 
-pub fn part1() -> usize {
-    let mut parts = INPUT.split("\n\n");
+pub fn part1(input: &str) -> usize {
+    let mut parts = input.split("\n\n");
     let seeds = parts.next().unwrap().split_once(": ").unwrap().1;
     let mut numbers: Vec<usize> = seeds.split(' ').map(|s| s.parse().unwrap()).collect();
     for part in parts {
@@ -66,7 +104,7 @@ pub fn part1() -> usize {
     *numbers.iter().min().unwrap()
 }
 
-pub fn part2() -> usize {
+pub fn part2(input: &str) -> usize {
     // copyable range type
     #[derive(Copy, Clone)]
     struct Range {
@@ -94,7 +132,7 @@ pub fn part2() -> usize {
         }
     }
 
-    let mut parts = INPUT.split("\n\n");
+    let mut parts = input.split("\n\n");
     let seeds = parts.next().unwrap().split_once(": ").unwrap().1;
     let mut ranges = Vec::new();
     let mut split = seeds.split(' ');
@@ -117,9 +155,9 @@ pub fn part2() -> usize {
                     continue;
                 };
                 let starts_before = r.start < src_start;
-                let starts_after = r.start > src_start + len;
+                let starts_after = r.start >= src_start + len;
                 let ends_after = r.end > src_start + len;
-                let ends_before = r.end < src_start;
+                let ends_before = r.end <= src_start;
                 if starts_before && ends_before || starts_after {
                     continue;
                 }
@@ -180,3 +218,95 @@ pub fn part2() -> usize {
         .min()
         .unwrap()
 }
+
+pub(crate) const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// Seed ranges, and maps that each move around some chunks of a 32 bit range
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    const MAX: u64 = 1 << 32;
+    let seeds: Vec<String> = (0..10 * scale)
+        .map(|_| {
+            let start = rng.gen_range(0..MAX / 2);
+            format!("{start} {}", rng.gen_range(1..MAX / 16))
+        })
+        .collect();
+    let mut out = format!("seeds: {}", seeds.join(" "));
+    for name in MAPS {
+        let mut cuts: Vec<u64> = (1..60 * scale).map(|_| rng.gen_range(1..MAX)).collect();
+        cuts.extend([0, MAX]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut chunks: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        // lay the chunks out again in a different order, then leave about
+        // half of them unmapped
+        chunks.shuffle(rng);
+        out.push_str(&format!("\n\n{name} map:"));
+        let mut dest = 0;
+        for (src, len) in chunks {
+            if rng.gen_bool(0.5) {
+                out.push_str(&format!("\n{dest} {src} {len}"));
+            }
+            dest += len;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day5>(EXAMPLE, 1), "35");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day5>(EXAMPLE, 2), "46");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        check(input)?;
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
+
+// Both parts read the races by column, so each distance has to end under its
+// time, as in
+//
+//     Time:      7  15   30
+//     Distance:  9  40  200
+fn check(input: &str) -> Result<(), ParseError> {
+    let mut lines = input.lines();
+    let time = lines.next().unwrap_or_default();
+    let distance = lines.next().unwrap_or(&input[input.len()..]);
+    if let Some(extra) = lines.next() {
+        return Err(ParseError::at(input, extra, "end of input"));
+    }
+    for (line, prefix) in [(time, "Time:"), (distance, "Distance:")] {
+        let Some(numbers) = line.strip_prefix(prefix) else {
+            return Err(ParseError::at(input, line, format!("'{prefix}'")));
+        };
+        if let Some(i) = numbers.find(|c: char| c != ' ' && !c.is_ascii_digit()) {
+            return Err(ParseError::at(input, &numbers[i..], "a number"));
+        }
+    }
+    // where each number ends
+    let ends = |line: &str| -> Vec<usize> {
+        let b = line.as_bytes();
+        (0..b.len())
+            .filter(|&i| b[i].is_ascii_digit() && !b.get(i + 1).is_some_and(u8::is_ascii_digit))
+            .collect()
+    };
+    let (time_ends, distance_ends) = (ends(time), ends(distance));
+    if time_ends.is_empty() {
+        return Err(ParseError::at(input, &time[time.len()..], "a number"));
+    }
+    if let Some(i) = (0..time_ends.len()).find(|&i| distance_ends.get(i) != Some(&time_ends[i])) {
+        let at = (time_ends[i] + 1).min(distance.len());
+        return Err(ParseError::at(
+            input,
+            &distance[at..],
+            "a distance ending under its time",
+        ));
+    }
+    if let Some(&end) = distance_ends.get(time_ends.len()) {
+        return Err(ParseError::at(input, &distance[end..], "end of line"));
+    }
+    Ok(())
+}
+
+fn calculate_number_of_wins(limit: u64, distance: u64) -> u32 {
+    /*
+    d = h * (l - h)
+    h * (l - h) > d
+    hl - h^2 > d
+    h^2 - hl + d = 0
+    solve for h with quadratic formula
+    h = (l +/- sqrt(l^2 - 4d)) / 2
+     */
+    let limit = limit as f64;
+    let distance = distance as f64;
+    let lo = (limit - (limit.powf(2.0) - 4.0 * distance).sqrt()) / 2.0;
+    let hi = (limit + (limit.powf(2.0) - 4.0 * distance).sqrt()) / 2.0;
+    (hi.ceil() - lo.floor()) as u32 - 1
+}
+
+struct Part1Iterator<'a> {
+    src_time: &'a [u8],
+    src_dist: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Part1Iterator<'a> {
+    pub fn new(s: &'a str) -> Self {
+        let src = s.as_bytes();
+        let newline = src.iter().position(|&b| b == b'\n').unwrap();
+        let (src_time, src_dist) = src.split_at(newline);
+        let src_time = &src_time[9..]; // skip 'Time:' and extra spaces used for alignment
+        let src_dist = &src_dist[10..]; // skip newline and 'Distance:'
+        Self {
+            src_time,
+            src_dist,
+            pos: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Part1Iterator<'a> {
+    type Item = (u64, u64);
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut time = 0;
+        let mut distance = 0;
+        let mut pos = self.pos;
+        while let Some((&t, &d)) = Option::zip(self.src_time.get(pos), self.src_dist.get(pos)) {
+            // reached space at the end of the number
+            if t == b' ' && time > 0 {
+                break;
+            }
+            if t != b' ' {
+                time = time * 10 + (t & 0xf) as u64;
+            }
+            if d != b' ' {
+                distance = distance * 10 + (d & 0xf) as u64;
+            }
+            pos += 1;
+        }
+        // didn't get any numbers
+        if time == 0 {
+            return None;
+        }
+        self.pos = pos;
+        Some((time, distance))
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    Part1Iterator::new(input)
+        .map(|(time, distance)| calculate_number_of_wins(time, distance) as usize)
+        .product()
+}
+
+fn parse_part2(input: &str) -> (u64, u64) {
+    let mut pos = 9;
+    let input = input.as_bytes();
+    while input[pos] == b' ' {
+        pos += 1;
+    }
+    let mut time = 0;
+    loop {
+        match input[pos] {
+            b' ' => (),
+            b'\n' => {
+                break;
+            }
+            n => {
+                debug_assert!(n.is_ascii_digit());
+                time = time * 10 + (n & 0xf) as u64;
+            }
+        }
+        pos += 1;
+    }
+    pos += 10;
+    let mut distance = 0;
+    while let Some(&n) = input.get(pos) {
+        pos += 1;
+        if n == b' ' {
+            continue;
+        }
+        debug_assert!(n.is_ascii_digit(), "'{}'", n as char);
+        distance = distance * 10 + (n & 0xf) as u64;
+    }
+
+    (time, distance)
+}
+
+pub fn part2(input: &str) -> usize {
+    let (time, distance) = parse_part2(input);
+    calculate_number_of_wins(time, distance) as usize
+}
+
+// Four races, each with a time of one or two digits and a distance of one
+// digit less than twice that, so the joined up part 2 race fits in a u64
+// and can still be won. There's nothing to scale up without overflowing.
+pub fn generate(rng: &mut StdRng, _scale: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..4)
+        .map(|_| {
+            let lo = 10u64.pow(rng.gen_range(0..=1));
+            let time = rng.gen_range(2 * lo + 1..10 * lo);
+            let distance = rng.gen_range(lo * lo..(time * time / 4).min(10 * lo * lo));
+            (time, distance)
+        })
+        .collect();
+    let mut time = "Time:    ".to_string();
+    let mut distance = "Distance:".to_string();
+    for (t, d) in races {
+        let width = d.to_string().len() + rng.gen_range(1..=3);
+        time.push_str(&format!("{t:>width$}"));
+        distance.push_str(&format!("{d:>width$}"));
+    }
+    format!("{time}\n{distance}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day6>(EXAMPLE, 1), "288");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day6>(EXAMPLE, 2), "71503");
+    }
+}
@@ -1,4 +1,31 @@
-const INPUT: &str = include_str!("inputs/7.txt");
+use crate::answer::Answer;
+use crate::solution::{parse_number, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        for line in input.lines() {
+            check_line(input, line)?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &&str) -> Answer {
+        part2(input).into()
+    }
+}
 
 #[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Debug, Hash)]
 enum Card {
@@ -159,6 +186,29 @@ enum Type {
     FiveOfAKind,
 }
 
+// `parse_input` reads hands by byte offset, so make sure every line looks
+// like `32T3K 765` first
+fn check_line(input: &str, line: &str) -> Result<(), ParseError> {
+    let cards = line.get(..5).unwrap_or(line);
+    if let Some(i) = cards.find(|c| !"23456789TJQKA".contains(c)) {
+        return Err(ParseError::at(input, &line[i..], "a card from '2' to 'A'"));
+    }
+    if cards.len() < 5 {
+        return Err(ParseError::at(
+            input,
+            &line[line.len()..],
+            "a hand of five cards",
+        ));
+    }
+    let Some(bid) = line[5..].strip_prefix(' ') else {
+        return Err(ParseError::at(input, &line[5..], "' '"));
+    };
+    if let Some(i) = bid.find(|c: char| !c.is_ascii_digit()) {
+        return Err(ParseError::at(input, &bid[i..], "a digit"));
+    }
+    parse_number::<u32>(input, bid).map(drop)
+}
+
 fn parse_input<const JOKER: bool>(inp: &str) -> Vec<Hand<JOKER>> {
     let mut result = Vec::with_capacity(inp.len() / 9);
     let src = inp.as_bytes();
@@ -201,8 +251,8 @@ fn parse_input<const JOKER: bool>(inp: &str) -> Vec<Hand<JOKER>> {
     result
 }
 
-pub fn part1() -> usize {
-    let mut bets = parse_input::<false>(INPUT);
+pub fn part1(input: &str) -> usize {
+    let mut bets = parse_input::<false>(input);
     // sort from weakest hand first
     bets.sort_unstable();
     bets.into_iter()
@@ -211,8 +261,8 @@ pub fn part1() -> usize {
         .sum()
 }
 
-pub fn part2() -> usize {
-    let mut bets = parse_input::<true>(INPUT);
+pub fn part2(input: &str) -> usize {
+    let mut bets = parse_input::<true>(input);
     // sort from weakest hand first
     bets.sort_unstable();
     bets.into_iter()
@@ -220,3 +270,42 @@ pub fn part2() -> usize {
         .map(|(rank, bet)| (rank + 1) * bet.2 as usize)
         .sum()
 }
+
+// Hands drawn from a few kinds of card each, so there are plenty of pairs
+// and better
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let hands: Vec<String> = (0..1000 * scale)
+        .map(|_| {
+            let n = rng.gen_range(1..=5);
+            let kinds: Vec<u8> = b"23456789TJQKA".choose_multiple(rng, n).copied().collect();
+            let hand: String = (0..5)
+                .map(|_| *kinds.choose(rng).unwrap() as char)
+                .collect();
+            format!("{hand} {}", rng.gen_range(1..=1000))
+        })
+        .collect();
+    hands.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day7>(EXAMPLE, 1), "6440");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day7>(EXAMPLE, 2), "5905");
+    }
+}
@@ -0,0 +1,296 @@
+use crate::answer::Answer;
+use crate::solution::{ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+    type Parsed<'a> = Network<'a>;
+
+    fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(network: &Network) -> Answer {
+        part1(network).into()
+    }
+
+    fn part2(network: &Network) -> Answer {
+        part2(network).into()
+    }
+}
+
+// As long as the input is alphabetic, we can use 5 bits per letter and store it as
+// a u16, meaning we can index into a reasonably sized array with it
+// sadly this means sample input doesn't work
+fn alpha_to_u16(b: [u8; 3]) -> Option<u16> {
+    if !b.iter().all(u8::is_ascii_uppercase) {
+        return None;
+    }
+    let b1 = ((b[0] - b'A') as u16) << 10;
+    let b2 = ((b[1] - b'A') as u16) << 5;
+    let b3 = ((b[2] - b'A') as u16);
+    Some(b1 | b2 | b3)
+}
+
+const AAA: u16 = 0;
+const ZZZ: u16 = 26425;
+
+// Parse a line like `AAA = (BBB, CCC)`
+fn parse_node(input: &str, line: &str) -> Result<(u16, (u16, u16)), ParseError> {
+    let rest = |at: usize| line.get(at..).unwrap_or(&line[line.len()..]);
+    let name = |at: usize| {
+        let name = line
+            .as_bytes()
+            .get(at..at + 3)
+            .and_then(|n| n.try_into().ok());
+        name.and_then(alpha_to_u16)
+            .ok_or_else(|| ParseError::at(input, rest(at), "a node name of three capital letters"))
+    };
+    let literal = |at: usize, s: &str| match line.get(at..at + s.len()) {
+        Some(found) if found == s => Ok(()),
+        _ => Err(ParseError::at(input, rest(at), format!("'{s}'"))),
+    };
+    let key = name(0)?;
+    literal(3, " = (")?;
+    let left = name(7)?;
+    literal(10, ", ")?;
+    let right = name(12)?;
+    literal(15, ")")?;
+    if line.len() > 16 {
+        return Err(ParseError::at(input, rest(16), "end of line"));
+    }
+    Ok((key, (left, right)))
+}
+
+fn make_map(input: &str, nodes: &str) -> Result<Box<[(u16, u16); u16::MAX as usize]>, ParseError> {
+    let mut data: Box<[(u16, u16); u16::MAX as usize]> =
+        vec![(0, 0); u16::MAX as usize].try_into().unwrap();
+    for line in nodes.lines() {
+        let (key, value) = parse_node(input, line)?;
+        data[key as usize] = value;
+    }
+    Ok(data)
+}
+
+pub struct Network<'a> {
+    directions: &'a str,
+    nodes: Box<[(u16, u16); u16::MAX as usize]>,
+}
+
+pub fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+    let (directions, nodes) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::at_offset(input, input.len(), "a blank line after the directions")
+    })?;
+    let bad_direction = directions.find(|c| c != 'L' && c != 'R');
+    if let Some(i) = bad_direction.or(directions.is_empty().then_some(0)) {
+        return Err(ParseError::at_offset(input, i, "'L' or 'R'"));
+    }
+    Ok(Network {
+        directions,
+        nodes: make_map(input, nodes)?,
+    })
+}
+
+pub fn part1(network: &Network) -> usize {
+    let Network { directions, nodes } = network;
+    let mut cur = AAA;
+    let mut iter = directions.bytes().cycle().enumerate();
+    for (_, dir) in iter.by_ref() {
+        let node = nodes[cur as usize];
+        if dir == b'L' {
+            cur = node.0
+        } else {
+            cur = node.1
+        };
+        if cur == ZZZ {
+            break;
+        }
+    }
+    iter.next().unwrap().0
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    fn gcd(mut a: usize, mut b: usize) -> usize {
+        while b != 0 {
+            let temp = b;
+            b = a % b;
+            a = temp;
+        }
+        a
+    }
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a * b / gcd(a, b)
+    }
+}
+
+pub fn part2(network: &Network) -> usize {
+    let Network { directions, nodes } = network;
+    let mut cur = Vec::with_capacity(u16::MAX as usize / 32);
+    cur.extend(
+        (0..u16::MAX)
+            .step_by(32)
+            .filter(|n| nodes[*n as usize].0 != 0),
+    );
+    let mut running_lcm = 1;
+
+    let mut iter = directions.bytes().cycle().enumerate();
+    for (i, dir) in iter {
+        if cur.is_empty() {
+            break;
+        }
+        for j in (0..cur.len()).rev() {
+            let nodes = nodes[cur[j] as usize];
+            let new = if dir == b'L' { nodes.0 } else { nodes.1 };
+            if new & 0b11111 == (b'Z' - b'A') as u16 {
+                cur.remove(j);
+                running_lcm = lcm(running_lcm, i + 1);
+            } else {
+                cur[j] = new;
+            }
+        }
+    }
+    // paths cycle between their second entry all the way up to Z
+    // the LCM of all cycle lengths gives us the point at which they all
+    // reach Z at once
+    running_lcm
+}
+
+fn u16_to_alpha(n: u16) -> String {
+    [n >> 10, (n >> 5) & 0b11111, n & 0b11111]
+        .map(|b| (b as u8 + b'A') as char)
+        .iter()
+        .collect()
+}
+
+// Six ghosts, each on its own loop of a prime multiple of the number of
+// directions, reaching their Z once per lap like in the real input. The
+// first ghost goes from AAA to ZZZ for part 1.
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    const GHOSTS: usize = 6;
+    let mut primes = [11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+    primes.shuffle(rng);
+    let primes = &primes[..GHOSTS];
+    // every node in a loop needs its own name
+    let mut names: Vec<u16> = (0..26 * 26 * 26)
+        .map(|n| (((n / 676) << 10) | ((n / 26 % 26) << 5) | (n % 26)) as u16)
+        .collect();
+    names.shuffle(rng);
+    let (mut starts, mut ends, mut others) = (vec![AAA], vec![ZZZ], vec![]);
+    for name in names {
+        match name & 0b11111 {
+            _ if name == AAA || name == ZZZ => {}
+            0 if starts.len() < GHOSTS => starts.push(name),
+            25 if ends.len() < GHOSTS => ends.push(name),
+            0 | 25 => {}
+            _ => others.push(name),
+        }
+    }
+    let directions_len = (5 * scale)
+        .min(others.len() / primes.iter().sum::<usize>())
+        .max(1);
+    let directions: Vec<u8> = (0..directions_len)
+        .map(|_| *b"LR".choose(rng).unwrap())
+        .collect();
+
+    let mut nodes = Vec::new();
+    let mut others = others.into_iter();
+    for (g, prime) in primes.iter().enumerate() {
+        let lap = prime * directions_len;
+        let mut path = vec![starts[g]];
+        path.extend(others.by_ref().take(lap - 1));
+        path.push(ends[g]);
+        for (i, &node) in path.iter().enumerate() {
+            // the Z leads back round to where the start went
+            let next = path.get(i + 1).copied().unwrap_or(path[1]);
+            nodes.push((node, directions[i % directions_len], next));
+        }
+    }
+    // the way not taken can lead anywhere but AAA
+    let decoys: Vec<u16> = nodes.iter().map(|n| n.0).filter(|&n| n != AAA).collect();
+    nodes.shuffle(rng);
+    let nodes: Vec<String> = nodes
+        .iter()
+        .map(|&(node, dir, next)| {
+            let decoy = *decoys.choose(rng).unwrap();
+            let (left, right) = if dir == b'L' {
+                (next, decoy)
+            } else {
+                (decoy, next)
+            };
+            format!(
+                "{} = ({}, {})",
+                u16_to_alpha(node),
+                u16_to_alpha(left),
+                u16_to_alpha(right)
+            )
+        })
+        .collect();
+    format!(
+        "{}\n\n{}",
+        String::from_utf8(directions).unwrap(),
+        nodes.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    // node names have to be letters, so the digits in the part 2 example
+    // are swapped for letters that don't end in A or Z
+    const EXAMPLE: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE3: &str = "\
+LR
+
+PPA = (PPB, XXX)
+PPB = (XXX, PPZ)
+PPZ = (PPB, XXX)
+QQA = (QQB, XXX)
+QQB = (QQC, QQC)
+QQC = (QQZ, QQZ)
+QQZ = (QQB, QQB)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day8>(EXAMPLE, 1), "2");
+        assert_eq!(solve::<Day8>(EXAMPLE2, 1), "6");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day8>(EXAMPLE3, 2), "6");
+    }
+
+    #[test]
+    fn lowercase_node() {
+        assert_eq!(
+            parse_error::<Day8>("RL\n\nAAA = (BBB, CCC)\nBBB = (AAA, zzz)"),
+            "day 8, line 4, column 13: expected a node name of three capital letters, found 'zzz'"
+        );
+    }
+}
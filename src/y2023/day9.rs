@@ -0,0 +1,137 @@
+use crate::answer::Answer;
+use crate::solution::{parse_number, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
+    type Parsed<'a> = Vec<Vec<isize>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(histories: &Vec<Vec<isize>>) -> Answer {
+        part1(histories).into()
+    }
+
+    fn part2(histories: &Vec<Vec<isize>>) -> Answer {
+        part2(histories).into()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+    input
+        .lines()
+        .map(|l| {
+            let history: Vec<isize> = l
+                .split(' ')
+                .map(|n| parse_number(input, n))
+                .collect::<Result<_, _>>()?;
+            // there has to be a difference to extrapolate from
+            if history.len() < 2 {
+                return Err(ParseError::at(input, &l[l.len()..], "another number"));
+            }
+            Ok(history)
+        })
+        .collect()
+}
+
+pub fn part1(histories: &[Vec<isize>]) -> isize {
+    histories
+        .iter()
+        .map(|nums| {
+            let mut diffs: Vec<Vec<isize>> = vec![nums.windows(2).map(|w| w[1] - w[0]).collect()];
+
+            loop {
+                let ld = diffs.last().unwrap();
+                if ld.iter().all(|&d| d == 0) {
+                    break;
+                }
+                diffs.push(ld.windows(2).map(|w| w[1] - w[0]).collect());
+            }
+            let diff_sums = diffs
+                .into_iter()
+                .rev()
+                .map(|d| *d.last().unwrap())
+                .sum::<isize>();
+            diff_sums + nums.last().unwrap()
+        })
+        .sum()
+}
+
+pub fn part2(histories: &[Vec<isize>]) -> isize {
+    histories
+        .iter()
+        .map(|nums| {
+            let mut diffs: Vec<Vec<isize>> = vec![nums.windows(2).map(|w| w[1] - w[0]).collect()];
+
+            loop {
+                let ld = diffs.last().unwrap();
+                if ld.iter().all(|&d| d == 0) {
+                    break;
+                }
+                diffs.push(ld.windows(2).map(|w| w[1] - w[0]).collect());
+            }
+            let start_diff = diffs
+                .into_iter()
+                .rev()
+                .fold(0isize, |acc, diff| diff[0] - acc);
+            nums[0] - start_diff
+        })
+        .sum()
+}
+
+// Histories that are polynomials of up to degree ten, built up from their
+// constant row of differences
+pub fn generate(rng: &mut StdRng, scale: usize) -> String {
+    let lines: Vec<String> = (0..200 * scale)
+        .map(|_| {
+            let mut row = vec![rng.gen_range(-10..=10isize); 21];
+            for _ in 0..rng.gen_range(0..=10) {
+                let mut value = rng.gen_range(-20..=20);
+                let mut above = vec![value];
+                for diff in &row[..20] {
+                    value += diff;
+                    above.push(value);
+                }
+                row = above;
+            }
+            let row: Vec<String> = row.iter().map(isize::to_string).collect();
+            row.join(" ")
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{parse_error, solve};
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve::<Day9>(EXAMPLE, 1), "114");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve::<Day9>(EXAMPLE, 2), "2");
+    }
+
+    #[test]
+    fn bad_number() {
+        assert_eq!(
+            parse_error::<Day9>("0 3 6 9\n1 3 x 10"),
+            "day 9, line 2, column 5: expected a number, found 'x'"
+        );
+    }
+}